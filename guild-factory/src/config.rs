@@ -1,4 +1,7 @@
-use guild_sc_config::InitArgs;
+use guild_sc_config::{
+    timelock::{ChangeId, PendingConfigChange, ProxyTrait as _},
+    InitArgs,
+};
 
 multiversx_sc::imports!();
 
@@ -42,6 +45,8 @@ pub trait ConfigModule {
         );
     }
 
+    /// Setters of the config SC are timelocked.
+    /// Once the timelock is set, a change must first be proposed through `proposeConfigChange`
     #[only_owner]
     #[endpoint(callConfigFunction)]
     fn call_config_function(
@@ -49,19 +54,67 @@ pub trait ConfigModule {
         function_name: ManagedBuffer,
         args: MultiValueEncoded<ManagedBuffer>,
     ) {
-        let config_mapper = self.config_sc_address();
-        require!(!config_mapper.is_empty(), "Config not deployed yet");
+        self.call_config_sc(function_name, args.to_vec());
+    }
+
+    #[only_owner]
+    #[endpoint(proposeConfigChange)]
+    fn propose_config_change(
+        &self,
+        function_name: ManagedBuffer,
+        args: MultiValueEncoded<ManagedBuffer>,
+    ) -> ChangeId {
+        let config_sc_address = self.get_config_sc_address();
+
+        self.config_proxy()
+            .contract(config_sc_address)
+            .propose_config_change(function_name, args)
+            .execute_on_dest_context()
+    }
+
+    #[only_owner]
+    #[endpoint(cancelConfigChange)]
+    fn cancel_config_change(&self, change_id: ChangeId) {
+        let config_sc_address = self.get_config_sc_address();
+
+        let _: IgnoreValue = self
+            .config_proxy()
+            .contract(config_sc_address)
+            .cancel_config_change(change_id)
+            .execute_on_dest_context();
+    }
+
+    /// Calls the config SC function with the arguments stored in the pending change
+    #[only_owner]
+    #[endpoint(executeConfigChange)]
+    fn execute_config_change(&self, change_id: ChangeId) {
+        let config_sc_address = self.get_config_sc_address();
+        let pending_change_mapper =
+            self.external_pending_config_change(config_sc_address, change_id);
+        require!(!pending_change_mapper.is_empty(), "Unknown config change");
 
-        let config_sc_address = config_mapper.get();
+        let pending_change = pending_change_mapper.get();
+        self.call_config_sc(pending_change.function_name, pending_change.args);
+    }
+
+    fn call_config_sc(&self, function_name: ManagedBuffer, args: ManagedVec<ManagedBuffer>) {
+        let config_sc_address = self.get_config_sc_address();
         let mut call_data =
             ContractCallNoPayment::<_, IgnoreValue>::new(config_sc_address, function_name);
-        for arg in args {
-            call_data = call_data.argument(&arg);
+        for arg in args.iter() {
+            call_data = call_data.argument(&*arg);
         }
 
         let _: IgnoreValue = call_data.execute_on_dest_context();
     }
 
+    fn get_config_sc_address(&self) -> ManagedAddress {
+        let config_mapper = self.config_sc_address();
+        require!(!config_mapper.is_empty(), "Config not deployed yet");
+
+        config_mapper.get()
+    }
+
     fn get_default_code_metadata(&self) -> CodeMetadata {
         CodeMetadata::PAYABLE_BY_SC | CodeMetadata::UPGRADEABLE | CodeMetadata::READABLE
     }
@@ -69,6 +122,13 @@ pub trait ConfigModule {
     #[proxy]
     fn config_proxy(&self) -> guild_sc_config::Proxy<Self::Api>;
 
+    #[storage_mapper_from_address("pendingChange")]
+    fn external_pending_config_change(
+        &self,
+        sc_addr: ManagedAddress,
+        change_id: ChangeId,
+    ) -> SingleValueMapper<PendingConfigChange<Self::Api>, ManagedAddress>;
+
     #[view(getConfigAddress)]
     #[storage_mapper("configScAddress")]
     fn config_sc_address(&self) -> SingleValueMapper<ManagedAddress>;
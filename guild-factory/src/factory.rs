@@ -6,6 +6,8 @@ multiversx_sc::derive_imports!();
 
 static UNKNOWN_GUILD_ERR_MSG: &[u8] = b"Unknown guild";
 
+pub type SuspensionReason = u32;
+
 #[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
 pub struct GuildLocalConfig<M: ManagedTypeApi> {
     pub farming_token_id: TokenIdentifier<M>,
@@ -110,6 +112,10 @@ pub trait FactoryModule: crate::config::ConfigModule + utils::UtilsModule {
         );
 
         self.require_known_guild(guild_id);
+        require!(
+            !self.suspended_guilds().contains(&guild_id),
+            "Guild suspended"
+        );
 
         let caller = self.blockchain().get_caller();
         let caller_id = self.user_ids().get_id_non_zero(&caller);
@@ -133,6 +139,11 @@ pub trait FactoryModule: crate::config::ConfigModule + utils::UtilsModule {
         self.get_all_guilds_in_mapper(&self.active_guilds())
     }
 
+    #[view(getAllSuspendedGuilds)]
+    fn get_all_suspended_guilds(&self) -> MultiValueEncoded<GetGuildResultType<Self::Api>> {
+        self.get_all_guilds_in_mapper(&self.suspended_guilds())
+    }
+
     #[view(getGuildId)]
     fn get_guild_id(&self, guild_address: ManagedAddress) -> AddressId {
         self.guild_ids().get_id_non_zero(&guild_address)
@@ -181,6 +192,10 @@ pub trait FactoryModule: crate::config::ConfigModule + utils::UtilsModule {
         mapper.clear();
 
         self.guild_master_for_guild(guild_id).clear();
+
+        if self.suspended_guilds().swap_remove(&guild_id) {
+            self.suspension_reason(guild_id).clear();
+        }
     }
 
     fn require_known_guild(&self, guild_id: AddressId) {
@@ -247,6 +262,13 @@ pub trait FactoryModule: crate::config::ConfigModule + utils::UtilsModule {
     #[storage_mapper("activeGuilds")]
     fn active_guilds(&self) -> UnorderedSetMapper<AddressId>;
 
+    #[storage_mapper("suspendedGuilds")]
+    fn suspended_guilds(&self) -> UnorderedSetMapper<AddressId>;
+
+    #[view(getSuspensionReason)]
+    #[storage_mapper("suspensionReason")]
+    fn suspension_reason(&self, guild_id: AddressId) -> SingleValueMapper<SuspensionReason>;
+
     #[storage_mapper("guildScForUser")]
     fn guild_sc_for_user(&self, user_id: AddressId) -> SingleValueMapper<AddressId>;
 
@@ -1,5 +1,11 @@
+use common_structs::{Epoch, Percent};
+use guild_sc::custom_rewards::ProxyTrait as _;
+use guild_sc::slashing::ProxyTrait as _;
+use guild_sc::user_actions::migration::{MigratedMember, ProxyTrait as _};
 use guild_sc::user_actions::stake_farm::ProxyTrait as _;
 
+use crate::factory::SuspensionReason;
+
 multiversx_sc::imports!();
 
 static INVALID_PAYMENT_ERR_MSG: &[u8] = b"Invalid payment";
@@ -51,6 +57,300 @@ pub trait GuildInteractionsModule:
             .execute_on_dest_context();
     }
 
+    /// Called by an active guild, moving the position of the original caller to another active guild.
+    /// Returns the new farm token, which is sent directly to the original caller.
+    #[payable("*")]
+    #[endpoint(switchGuild)]
+    fn switch_guild(
+        &self,
+        guild: ManagedAddress,
+        original_caller: ManagedAddress,
+        compounded_reward: BigUint,
+    ) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.guild_ids().get_id_non_zero(&caller);
+        let guild_id = self.guild_ids().get_id_non_zero(&guild);
+        require!(caller_id != guild_id, "Must switch to another guild");
+        self.require_active_guild(caller_id);
+        self.require_active_guild(guild_id);
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let last_switch_mapper = self.last_guild_switch_epoch(&original_caller);
+        if !last_switch_mapper.is_empty() {
+            let cooldown_epochs = self.guild_switch_cooldown_epochs().get();
+            require!(
+                current_epoch >= last_switch_mapper.get() + cooldown_epochs,
+                "Switch cooldown not over"
+            );
+        }
+        last_switch_mapper.set(current_epoch);
+
+        let payment = self.check_payment_is_farming_token();
+        self.guild_sc_proxy(guild)
+            .receive_switched_position(original_caller, compounded_reward)
+            .with_esdt_transfer(payment)
+            .execute_on_dest_context()
+    }
+
+    #[only_owner]
+    #[endpoint(setGuildSwitchCooldownEpochs)]
+    fn set_guild_switch_cooldown_epochs(&self, cooldown_epochs: Epoch) {
+        self.guild_switch_cooldown_epochs().set(cooldown_epochs);
+    }
+
+    /// Slashes the stake of the guild master of an active guild.
+    /// The slashed tokens are sent to the slashing treasury, or distributed to the guild members otherwise.
+    #[only_admin]
+    #[endpoint(slashGuildMaster)]
+    fn slash_guild_master(
+        &self,
+        guild: ManagedAddress,
+        percentage: Percent,
+        send_to_treasury: bool,
+    ) -> BigUint {
+        let guild_id = self.guild_ids().get_id_non_zero(&guild);
+        self.require_active_guild(guild_id);
+
+        let opt_treasury = if send_to_treasury {
+            let treasury_mapper = self.slashing_treasury();
+            require!(!treasury_mapper.is_empty(), "No slashing treasury");
+
+            OptionalValue::Some(treasury_mapper.get())
+        } else {
+            OptionalValue::None
+        };
+
+        self.guild_sc_proxy(guild)
+            .slash_guild_master(percentage, opt_treasury)
+            .execute_on_dest_context()
+    }
+
+    #[only_owner]
+    #[endpoint(setSlashingTreasury)]
+    fn set_slashing_treasury(&self, treasury: ManagedAddress) {
+        require!(!treasury.is_zero(), "Invalid treasury");
+
+        self.slashing_treasury().set(treasury);
+    }
+
+    /// Stops the reward production of an active guild, freeing its active guild slot.
+    /// Members may still claim the rewards produced so far, and exit the guild.
+    #[only_admin]
+    #[endpoint(suspendGuild)]
+    fn suspend_guild(&self, guild: ManagedAddress, reason: SuspensionReason) {
+        let guild_id = self.guild_ids().get_id_non_zero(&guild);
+        self.require_active_guild(guild_id);
+
+        let _: IgnoreValue = self
+            .guild_sc_proxy(guild.clone())
+            .stop_produce_rewards_endpoint()
+            .execute_on_dest_context();
+
+        self.active_guilds().swap_remove(&guild_id);
+        let _ = self.suspended_guilds().insert(guild_id);
+        self.suspension_reason(guild_id).set(reason);
+
+        let caller = self.blockchain().get_caller();
+        self.guild_suspended_event(&caller, &guild, reason);
+    }
+
+    /// The guild produces rewards again, if an active guild slot is available
+    #[only_admin]
+    #[endpoint(unsuspendGuild)]
+    fn unsuspend_guild(&self, guild: ManagedAddress) {
+        let guild_id = self.guild_ids().get_id_non_zero(&guild);
+        require!(
+            self.suspended_guilds().contains(&guild_id),
+            "Guild not suspended"
+        );
+
+        let current_active_guilds = self.get_current_active_guilds();
+        let max_active_guilds = self.max_active_guilds().get();
+        require!(
+            current_active_guilds < max_active_guilds,
+            "May not start another guild at this point"
+        );
+
+        self.start_produce_rewards(guild.clone());
+
+        let _ = self.suspended_guilds().swap_remove(&guild_id);
+        self.suspension_reason(guild_id).clear();
+        let _ = self.active_guilds().insert(guild_id);
+
+        let caller = self.blockchain().get_caller();
+        self.guild_unsuspended_event(&caller, &guild);
+    }
+
+    /// Withdraws the protocol fees accrued by the given guilds to the protocol fee treasury.
+    /// Closed guilds are included, as their fees accrued before closing.
+    #[only_admin]
+    #[endpoint(withdrawProtocolFees)]
+    fn withdraw_protocol_fees(&self, guilds: MultiValueEncoded<ManagedAddress>) -> BigUint {
+        let treasury_mapper = self.protocol_fee_treasury();
+        require!(!treasury_mapper.is_empty(), "No protocol fee treasury");
+
+        let treasury = treasury_mapper.get();
+        let mut total_fees = BigUint::zero();
+        for guild in guilds {
+            let guild_id = self.guild_ids().get_id(&guild);
+            require!(
+                self.deployed_guilds().contains(&guild_id) || self.closed_guilds().contains(&guild),
+                "Unknown guild"
+            );
+
+            let fees: BigUint = self
+                .guild_sc_proxy(guild.clone())
+                .withdraw_protocol_fees(treasury.clone())
+                .execute_on_dest_context();
+
+            self.withdrawn_protocol_fees(&guild)
+                .update(|withdrawn| *withdrawn += &fees);
+            total_fees += fees;
+        }
+
+        self.total_withdrawn_protocol_fees()
+            .update(|withdrawn| *withdrawn += &total_fees);
+
+        total_fees
+    }
+
+    #[only_owner]
+    #[endpoint(setProtocolFeeTreasury)]
+    fn set_protocol_fee_treasury(&self, treasury: ManagedAddress) {
+        require!(!treasury.is_zero(), "Invalid treasury");
+
+        self.protocol_fee_treasury().set(treasury);
+    }
+
+    /// Migrates the next `max_members` members of a closed guild.
+    /// Each member is moved to their fallback guild if it is active, or to the destination otherwise.
+    /// Only admins may choose the destination, while other callers use the default migration guild.
+    #[endpoint(migrateClosedGuildMembers)]
+    fn migrate_closed_guild_members(
+        &self,
+        guild: ManagedAddress,
+        max_members: usize,
+        opt_destination: OptionalValue<ManagedAddress>,
+    ) -> usize {
+        self.require_closed_guild(&guild);
+
+        let destination = match opt_destination {
+            OptionalValue::Some(destination) => {
+                self.require_caller_is_admin();
+
+                destination
+            }
+            OptionalValue::None => {
+                let default_guild_mapper = self.default_migration_guild();
+                require!(
+                    !default_guild_mapper.is_empty(),
+                    "No default migration guild"
+                );
+
+                default_guild_mapper.get()
+            }
+        };
+        let destination_id = self.guild_ids().get_id_non_zero(&destination);
+        self.require_active_guild(destination_id);
+
+        let migrated_members: MultiValueEncoded<MigratedMember<Self::Api>> = self
+            .guild_sc_proxy(guild.clone())
+            .migrate_members(max_members)
+            .execute_on_dest_context();
+
+        let guild_config = self.guild_local_config().get();
+        let mut members_count = 0;
+        for migrated_member in migrated_members {
+            let (member, staked_tokens, compounded_reward) = migrated_member.into_tuple();
+            let member_destination = self.get_member_migration_guild(&member, &destination);
+            let can_receive: bool = self
+                .guild_sc_proxy(member_destination.clone())
+                .can_receive_switched_position(
+                    member.clone(),
+                    staked_tokens.clone(),
+                    compounded_reward.clone(),
+                )
+                .execute_on_dest_context();
+            if !can_receive {
+                self.unmigrated_tokens(&member)
+                    .update(|unmigrated| *unmigrated += &staked_tokens);
+                self.member_migration_failed_event(&guild, &member, &staked_tokens);
+
+                continue;
+            }
+
+            let payment =
+                EsdtTokenPayment::new(guild_config.farming_token_id.clone(), 0, staked_tokens);
+            let _: EsdtTokenPayment = self
+                .guild_sc_proxy(member_destination)
+                .receive_switched_position(member, compounded_reward)
+                .with_esdt_transfer(payment)
+                .execute_on_dest_context();
+
+            members_count += 1;
+        }
+
+        members_count
+    }
+
+    /// Sends back the stake of the caller that could not be moved to another guild when their guild closed
+    #[endpoint(claimUnmigratedTokens)]
+    fn claim_unmigrated_tokens(&self) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        let amount = self.unmigrated_tokens(&caller).take();
+        require!(amount > 0, "No unmigrated tokens");
+
+        let guild_config = self.guild_local_config().get();
+        let payment = EsdtTokenPayment::new(guild_config.farming_token_id, 0, amount);
+        self.send().direct_non_zero_esdt_payment(&caller, &payment);
+
+        payment
+    }
+
+    #[only_admin]
+    #[endpoint(setDefaultMigrationGuild)]
+    fn set_default_migration_guild(&self, guild: ManagedAddress) {
+        let guild_id = self.guild_ids().get_id_non_zero(&guild);
+        self.require_known_guild(guild_id);
+
+        self.default_migration_guild().set(guild);
+    }
+
+    /// The guild the caller's stake is migrated to if their guild closes
+    #[endpoint(setMigrationFallbackGuild)]
+    fn set_migration_fallback_guild(&self, guild: ManagedAddress) {
+        let guild_id = self.guild_ids().get_id_non_zero(&guild);
+        self.require_known_guild(guild_id);
+
+        let caller = self.blockchain().get_caller();
+        self.migration_fallback_guild(&caller).set(guild);
+    }
+
+    #[endpoint(clearMigrationFallbackGuild)]
+    fn clear_migration_fallback_guild(&self) {
+        let caller = self.blockchain().get_caller();
+        self.migration_fallback_guild(&caller).clear();
+    }
+
+    fn get_member_migration_guild(
+        &self,
+        member: &ManagedAddress,
+        destination: &ManagedAddress,
+    ) -> ManagedAddress {
+        let fallback_guild_mapper = self.migration_fallback_guild(member);
+        if fallback_guild_mapper.is_empty() {
+            return destination.clone();
+        }
+
+        let fallback_guild = fallback_guild_mapper.get();
+        let fallback_guild_id = self.guild_ids().get_id(&fallback_guild);
+        if !self.active_guilds().contains(&fallback_guild_id) {
+            return destination.clone();
+        }
+
+        fallback_guild
+    }
+
     #[payable("*")]
     #[endpoint(depositRewardsGuild)]
     fn deposit_rewards_guild(&self) {
@@ -78,6 +378,65 @@ pub trait GuildInteractionsModule:
         self.active_guilds().swap_remove(&caller_id);
     }
 
+    /// Called by an active guild that stopped producing rewards, as its guild master's stake was too low.
+    /// The guild master may resume the guild after topping up their stake.
+    #[endpoint(deactivateGuild)]
+    fn deactivate_guild(&self) {
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.guild_ids().get_id_non_zero(&caller);
+        self.require_active_guild(caller_id);
+
+        self.active_guilds().swap_remove(&caller_id);
+
+        self.guild_deactivated_event(&caller);
+    }
+
+    /// Called by a closed guild whose guild master cancelled the closing, registering it again as active
+    #[endpoint(reopenGuild)]
+    fn reopen_guild(&self) {
+        let caller = self.blockchain().get_caller();
+        self.require_closed_guild(&caller);
+
+        let current_active_guilds = self.get_current_active_guilds();
+        let max_active_guilds = self.max_active_guilds().get();
+        require!(
+            current_active_guilds < max_active_guilds,
+            "May not start another guild at this point"
+        );
+
+        let guild_master = self.external_guild_master_address(caller.clone()).get();
+        let guild_master_id = self.user_ids().get_id_or_insert(&guild_master);
+        let guild_mapper = self.guild_sc_for_user(guild_master_id);
+        require!(guild_mapper.is_empty(), "Already have a guild deployed");
+
+        let _ = self.closed_guilds().swap_remove(&caller);
+
+        let guild_id = self.guild_ids().insert_new(&caller);
+        let _ = self.deployed_guilds().insert(guild_id);
+        self.guild_master_for_guild(guild_id).set(guild_master_id);
+        guild_mapper.set(guild_id);
+
+        let _ = self.active_guilds().insert(guild_id);
+    }
+
+    /// Called by a guild after its members voted to replace the guild master, also while the guild is not active
+    #[endpoint(transferGuildMaster)]
+    fn transfer_guild_master(&self, new_guild_master: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.guild_ids().get_id_non_zero(&caller);
+        self.require_known_guild(caller_id);
+
+        let new_guild_master_id = self.user_ids().get_id_or_insert(&new_guild_master);
+        let new_guild_mapper = self.guild_sc_for_user(new_guild_master_id);
+        require!(new_guild_mapper.is_empty(), "Already have a guild deployed");
+
+        let old_guild_master_id = self.guild_master_for_guild(caller_id).get();
+        self.guild_sc_for_user(old_guild_master_id).clear();
+        self.guild_master_for_guild(caller_id)
+            .set(new_guild_master_id);
+        new_guild_mapper.set(caller_id);
+    }
+
     #[only_admin]
     #[payable("*")]
     #[endpoint(depositRewardsAdmins)]
@@ -110,10 +469,76 @@ pub trait GuildInteractionsModule:
         );
     }
 
+    fn require_active_guild(&self, guild_id: AddressId) {
+        require!(self.active_guilds().contains(&guild_id), "Guild not active");
+    }
+
     #[proxy]
     fn guild_sc_proxy(&self, sc_address: ManagedAddress) -> guild_sc::Proxy<Self::Api>;
 
+    #[event("guildSuspendedEvent")]
+    fn guild_suspended_event(
+        &self,
+        #[indexed] admin: &ManagedAddress,
+        #[indexed] guild: &ManagedAddress,
+        #[indexed] reason: SuspensionReason,
+    );
+
+    #[event("guildDeactivatedEvent")]
+    fn guild_deactivated_event(&self, #[indexed] guild: &ManagedAddress);
+
+    #[event("guildUnsuspendedEvent")]
+    fn guild_unsuspended_event(
+        &self,
+        #[indexed] admin: &ManagedAddress,
+        #[indexed] guild: &ManagedAddress,
+    );
+
+    #[event("memberMigrationFailedEvent")]
+    fn member_migration_failed_event(
+        &self,
+        #[indexed] guild: &ManagedAddress,
+        #[indexed] member: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+    );
+
     #[view(getClosedGuilds)]
     #[storage_mapper("closedGuilds")]
     fn closed_guilds(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getGuildSwitchCooldownEpochs)]
+    #[storage_mapper("guildSwitchCooldownEpochs")]
+    fn guild_switch_cooldown_epochs(&self) -> SingleValueMapper<Epoch>;
+
+    #[view(getLastGuildSwitchEpoch)]
+    #[storage_mapper("lastGuildSwitchEpoch")]
+    fn last_guild_switch_epoch(&self, user: &ManagedAddress) -> SingleValueMapper<Epoch>;
+
+    #[view(getDefaultMigrationGuild)]
+    #[storage_mapper("defaultMigrationGuild")]
+    fn default_migration_guild(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getMigrationFallbackGuild)]
+    #[storage_mapper("migrationFallbackGuild")]
+    fn migration_fallback_guild(&self, user: &ManagedAddress) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getUnmigratedTokens)]
+    #[storage_mapper("unmigratedTokens")]
+    fn unmigrated_tokens(&self, member: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getSlashingTreasury)]
+    #[storage_mapper("slashingTreasury")]
+    fn slashing_treasury(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getProtocolFeeTreasury)]
+    #[storage_mapper("protocolFeeTreasury")]
+    fn protocol_fee_treasury(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getWithdrawnProtocolFeesForGuild)]
+    #[storage_mapper("withdrawnProtocolFees")]
+    fn withdrawn_protocol_fees(&self, guild: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalWithdrawnProtocolFees)]
+    #[storage_mapper("totalWithdrawnProtocolFees")]
+    fn total_withdrawn_protocol_fees(&self) -> SingleValueMapper<BigUint>;
 }
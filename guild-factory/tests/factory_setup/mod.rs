@@ -173,8 +173,9 @@ where
 
         // init config SC
 
+        let factory_addr = factory_wrapper.address_ref().clone();
         b_mock
-            .execute_tx(&first_owner_addr, &config_wrapper, &rust_zero, |sc| {
+            .execute_tx(&factory_addr, &config_wrapper, &rust_zero, |sc| {
                 sc.init(InitArgs {
                     total_staking_tokens_minted: managed_biguint!(TOTAL_STAKING_TOKENS_MINTED),
                     max_staked_tokens: managed_biguint!(i64::MAX),
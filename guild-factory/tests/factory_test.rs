@@ -1,26 +1,47 @@
 pub mod factory_setup;
 
+use energy_factory::energy::{Energy, EnergyModule};
 use factory_setup::*;
-use guild_factory::factory::FactoryModule;
+use guild_factory::{factory::FactoryModule, guild_interactions::GuildInteractionsModule};
 use guild_sc::{
-    tiered_rewards::total_tokens::TokenPerTierModule,
+    boosted_yields::{BoostedYieldsModule, EPOCHS_IN_WEEK},
+    collateral::CollateralModule,
+    config::ConfigModule,
+    custom_rewards::CustomRewardsModule,
+    governance::{GovernanceModule, ProposalAction, ProposalStatus},
+    officers::OfficersModule,
+    reward_distribution::RewardDistributionModule,
+    rewards::RewardsModule,
+    sponsor_campaign::SponsorCampaignModule,
+    tiered_rewards::{read_config::ReadConfigModule, total_tokens::TokenPerTierModule},
     tokens::{
         farm_token::FarmTokenModule, request_id::RequestIdModule,
         token_attributes::StakingFarmTokenAttributes, unbond_token::UnbondTokenModule,
     },
     user_actions::{
-        claim_stake_farm_rewards::ClaimStakeFarmRewardsModule,
-        compound_stake_farm_rewards::CompoundStakeFarmRewardsModule, migration::MigrationModule,
-        stake_farm::StakeFarmModule, unbond_farm::UnbondFarmModule,
-        unstake_farm::UnstakeFarmModule,
+        claim_stake_farm_rewards::ClaimStakeFarmRewardsModule, close_guild::CloseGuildModule,
+        compound_stake_farm_rewards::CompoundStakeFarmRewardsModule,
+        distribute_rewards::DistributeRewardsModule, migration::MigrationModule,
+        stake_farm::StakeFarmModule, unbond_epochs::UnbondEpochsModule,
+        unbond_farm::UnbondFarmModule, unstake_farm::UnstakeFarmModule,
     },
     FarmStaking,
 };
-use guild_sc_config::tiers::{TierModule, MAX_PERCENT};
+use guild_sc_config::{
+    boosted_yields::BoostedYieldsConfigModule,
+    config_events::ConfigEventsModule,
+    global_config::{
+        ClosedGuildExitMode, GlobalConfigModule, STAKED_PERCENT_AVERAGE_WINDOW_SECONDS,
+    },
+    roles::{ConfigRole, RolesModule},
+    tier_types::{TierCurveMode, UserRewardTier},
+    tiers::{TierModule, MAX_PERCENT},
+    timelock::{TimelockModule, CONFIG_CHANGE_EXPIRY_EPOCHS},
+};
 use multiversx_sc::{
-    codec::Empty,
+    codec::{Empty, TopEncode},
     imports::{OptionalValue, StorageTokenWrapper},
-    types::EsdtLocalRole,
+    types::{Address, BigInt, EsdtLocalRole, ManagedBuffer, MultiValueEncoded},
 };
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_buffer, managed_token_id, rust_biguint,
@@ -762,10 +783,11 @@ fn set_apr_test() {
         guild_sc_config::contract_obj,
         guild_factory::contract_obj,
     );
+    let factory_address = farm_setup.factory_wrapper.address_ref().clone();
     farm_setup
         .b_mock
         .execute_tx(
-            &farm_setup.first_owner_address,
+            &factory_address,
             &farm_setup.config_wrapper,
             &rust_biguint!(0),
             |sc| {
@@ -1240,3 +1262,4053 @@ fn calculate_rewards_only_guild_master_test() {
         })
         .assert_ok();
 }
+
+#[test]
+fn timelocked_config_change_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+    let timelock_epochs = 3;
+    let new_min_stake = 1_000u64;
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_timelock_epochs(timelock_epochs);
+            },
+        )
+        .assert_ok();
+
+    // setters may not be called without a proposal
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_min_stake_user(managed_biguint!(new_min_stake));
+            },
+        )
+        .assert_user_error("Config change was not proposed");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut args = MultiValueEncoded::new();
+                args.push(managed_biguint!(new_min_stake).to_bytes_be_buffer());
+
+                let change_id = sc.propose_config_change(managed_buffer!(b"setMinStakeUser"), args);
+                assert_eq!(change_id, 1);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_min_stake_user(managed_biguint!(new_min_stake));
+            },
+        )
+        .assert_user_error("Config change still timelocked");
+
+    setup.b_mock.set_block_epoch(timelock_epochs);
+
+    // a different value does not match the proposal
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_min_stake_user(managed_biguint!(new_min_stake + 1));
+            },
+        )
+        .assert_user_error("Config change was not proposed");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_min_stake_user(managed_biguint!(new_min_stake));
+
+                assert_eq!(sc.min_stake_user().get(), managed_biguint!(new_min_stake));
+                assert!(sc.pending_change_ids().is_empty());
+            },
+        )
+        .assert_ok();
+
+    // only known timelocked setters may be proposed, by addresses whose role may call them
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc
+                    .propose_config_change(managed_buffer!(b"grantRole"), MultiValueEncoded::new());
+            },
+        )
+        .assert_user_error("Unknown timelocked setter");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut args = MultiValueEncoded::new();
+                args.push(managed_biguint!(new_min_stake).to_bytes_be_buffer());
+
+                let _ = sc.propose_config_change(managed_buffer!(b"setMinStakeUser"), args);
+            },
+        )
+        .assert_user_error("Permission denied");
+
+    let mut expiring_change_id = 0;
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut args = MultiValueEncoded::new();
+                args.push(managed_biguint!(new_min_stake).to_bytes_be_buffer());
+
+                expiring_change_id =
+                    sc.propose_config_change(managed_buffer!(b"setMinStakeUser"), args);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut args = MultiValueEncoded::new();
+                args.push(managed_biguint!(new_min_stake).to_bytes_be_buffer());
+
+                let _ = sc.propose_config_change(managed_buffer!(b"setMinStakeUser"), args);
+            },
+        )
+        .assert_user_error("Config change already proposed");
+
+    // changes expire if not executed in time, and may then be removed by anyone
+    setup
+        .b_mock
+        .set_block_epoch(timelock_epochs * 2 + CONFIG_CHANGE_EXPIRY_EPOCHS + 1);
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_min_stake_user(managed_biguint!(new_min_stake));
+            },
+        )
+        .assert_user_error("Config change expired");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.cancel_config_change(expiring_change_id);
+
+                assert!(sc.pending_change_ids().is_empty());
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn linear_user_tiers_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut user_tiers = MultiValueEncoded::new();
+                user_tiers.push((5_000, 1_000).into());
+                user_tiers.push((MAX_PERCENT, 3_000).into());
+                sc.update_user_tiers(user_tiers);
+
+                sc.set_user_tiers_curve_mode(TierCurveMode::Linear);
+            },
+        )
+        .assert_ok();
+
+    // equal APRs are only valid for linear curves
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_user_tier_apr(5_000, 3_000);
+                sc.set_user_tiers_curve_mode(TierCurveMode::Step);
+            },
+        )
+        .assert_user_error("Invalid APR");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.update_all();
+
+                assert_eq!(sc.find_user_tier_apr(2_500), 1_000);
+                assert_eq!(sc.find_user_tier_apr(5_000), 1_000);
+                assert_eq!(sc.find_user_tier_apr(7_500), 2_000);
+                assert_eq!(sc.find_user_tier_apr(MAX_PERCENT), 3_000);
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn config_roles_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+    let pauser_address = setup.user_address.clone();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &pauser_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.pause_all_guilds();
+            },
+        )
+        .assert_user_error("Permission denied");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.grant_role(ConfigRole::Pauser, managed_address!(&pauser_address));
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &pauser_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.pause_all_guilds();
+            },
+        )
+        .assert_ok();
+
+    // pauser may not change emissions
+    setup
+        .b_mock
+        .execute_tx(
+            &pauser_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_per_block_reward_amount(managed_biguint!(1));
+            },
+        )
+        .assert_user_error("Permission denied");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.revoke_role(ConfigRole::Pauser, managed_address!(&pauser_address));
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &pauser_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.unpause_all_guilds();
+            },
+        )
+        .assert_user_error("Permission denied");
+}
+
+#[test]
+fn config_changelog_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_seconds_per_block(12);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.config_wrapper, |sc| {
+            let last_id = sc.last_changelog_entry_id().get();
+            let mut changelog = sc.get_config_changelog(last_id, 10).into_iter();
+            let (entry_id, entry) = changelog.next().unwrap().into_tuple();
+            assert!(changelog.next().is_none());
+
+            assert_eq!(entry_id, last_id);
+            assert_eq!(entry.setting, managed_buffer!(b"setSecondsPerBlock"));
+            assert_eq!(entry.old_value, managed_buffer!(&[6u8]));
+            assert_eq!(entry.new_value, managed_buffer!(&[12u8]));
+            assert_eq!(entry.caller, managed_address!(&factory_address));
+
+            // init values are logged as well
+            let full_changelog = sc.get_config_changelog(0, 100);
+            assert_eq!(full_changelog.len() as u64, last_id);
+        })
+        .assert_ok();
+
+    // tier APR changes log the tier they belong to
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_user_tier_apr(MAX_PERCENT, MAX_APR - 1);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.config_wrapper, |sc| {
+            let last_id = sc.last_changelog_entry_id().get();
+            let (_, entry) = sc
+                .get_config_changelog(last_id, 1)
+                .into_iter()
+                .next()
+                .unwrap()
+                .into_tuple();
+
+            let old_tier = UserRewardTier {
+                max_percentage_staked: MAX_PERCENT,
+                apr: MAX_APR,
+            };
+            let new_tier = UserRewardTier {
+                max_percentage_staked: MAX_PERCENT,
+                apr: MAX_APR - 1,
+            };
+            assert_eq!(entry.setting, managed_buffer!(b"setUserTierApr"));
+            assert_eq!(entry.old_value, encode_to_buffer(&old_tier));
+            assert_eq!(entry.new_value, encode_to_buffer(&new_tier));
+        })
+        .assert_ok();
+}
+
+fn encode_to_buffer<T: TopEncode>(value: &T) -> ManagedBuffer<DebugApi> {
+    let mut buffer = ManagedBuffer::new();
+    value.top_encode(&mut buffer).unwrap();
+
+    buffer
+}
+
+#[test]
+fn unbond_epochs_override_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+    let guild_master_address = setup.first_owner_address.clone();
+
+    // only guild master may set the override
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_user_unbond_epochs(10);
+            },
+        )
+        .assert_user_error("Only guild master may call this endpoint");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &guild_master_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_user_unbond_epochs(10);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            assert_eq!(sc.get_user_unbond_epochs(), 10);
+        })
+        .assert_ok();
+
+    // other guilds still use the config SC value
+    setup
+        .b_mock
+        .execute_query(&setup.second_farm_wrapper, |sc| {
+            assert_eq!(sc.get_user_unbond_epochs(), MIN_UNBOND_EPOCHS);
+        })
+        .assert_ok();
+
+    // lower the bounds - override is clamped
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_unbond_epochs_bounds(2, 8);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            assert_eq!(sc.get_user_unbond_epochs(), 8);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &guild_master_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_user_unbond_epochs(9);
+            },
+        )
+        .assert_user_error("Invalid unbond epochs");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_min_unbond_epochs_user(9);
+            },
+        )
+        .assert_user_error("Invalid min unbond epochs");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_min_unbond_epochs_user(7);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.second_farm_wrapper, |sc| {
+            assert_eq!(sc.get_user_unbond_epochs(), 7);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn stake_farm_for_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let payer_address = setup.b_mock.create_user_account(&rust_biguint!(0));
+    let beneficiary_address = setup.user_address.clone();
+    let guild_master_address = setup.first_owner_address.clone();
+    let farm_in_amount = 100_000_000;
+    setup.b_mock.set_esdt_balance(
+        &payer_address,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(farm_in_amount * 2),
+    );
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &payer_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_for(managed_address!(&guild_master_address));
+            },
+        )
+        .assert_user_error("May not stake on behalf of guild master");
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &payer_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let new_farm_token = sc.stake_farm_for(managed_address!(&beneficiary_address));
+                assert_eq!(new_farm_token.token_nonce, 2);
+                assert_eq!(new_farm_token.amount, managed_biguint!(farm_in_amount));
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance::<Empty>(
+        &beneficiary_address,
+        FARM_TOKEN_ID,
+        2,
+        &rust_biguint!(farm_in_amount),
+        None,
+    );
+    setup.b_mock.check_nft_balance::<Empty>(
+        &payer_address,
+        FARM_TOKEN_ID,
+        2,
+        &rust_biguint!(0),
+        None,
+    );
+    setup.b_mock.check_esdt_balance(
+        &payer_address,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(farm_in_amount),
+    );
+
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            assert_eq!(
+                sc.get_user_staked_tokens(managed_address!(&beneficiary_address)),
+                managed_biguint!(farm_in_amount)
+            );
+            assert_eq!(
+                sc.get_user_staked_tokens(managed_address!(&payer_address)),
+                managed_biguint!(0)
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn claim_rewards_to_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let rewards_receiver = setup.b_mock.create_user_account(&rust_biguint!(0));
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup.set_block_nonce(10);
+    setup.set_block_epoch(5);
+
+    let mut rewards_amount = 0;
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (new_farm_token, rewards_payment) = sc
+                    .claim_rewards_to(managed_address!(&rewards_receiver))
+                    .into_tuple();
+                assert_eq!(new_farm_token.token_nonce, 3);
+                assert!(rewards_payment.amount > 0);
+
+                rewards_amount = rewards_payment.amount.to_u64().unwrap();
+            },
+        )
+        .assert_ok();
+
+    // farm token goes back to the owner, rewards to the receiver
+    setup.b_mock.check_nft_balance::<Empty>(
+        &setup.user_address,
+        FARM_TOKEN_ID,
+        3,
+        &rust_biguint!(farm_in_amount),
+        None,
+    );
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_RIDE_TOKENS - farm_in_amount),
+    );
+    setup.b_mock.check_esdt_balance(
+        &rewards_receiver,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(rewards_amount),
+    );
+
+    setup.set_block_nonce(20);
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            3,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (unbond_token, rewards_payment) = sc
+                    .unstake_farm_to(managed_address!(&rewards_receiver))
+                    .into_tuple();
+                assert_eq!(unbond_token.amount, managed_biguint!(farm_in_amount));
+
+                rewards_amount += rewards_payment.amount.to_u64().unwrap();
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_RIDE_TOKENS - farm_in_amount),
+    );
+    setup.b_mock.check_esdt_balance(
+        &rewards_receiver,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(rewards_amount),
+    );
+}
+
+#[test]
+fn distribute_rewards_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let keeper = setup.b_mock.create_user_account(&rust_biguint!(0));
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            assert!(sc
+                .members()
+                .contains(&managed_address!(&setup.user_address)));
+        })
+        .assert_ok();
+
+    setup.set_block_nonce(10);
+    setup.set_block_epoch(5);
+
+    // invalid page size
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.distribute_rewards(0);
+            },
+        )
+        .assert_user_error("Invalid number of members");
+
+    let mut distributed_amount = 0;
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let total_distributed = sc.distribute_rewards(10);
+                assert!(total_distributed > 0);
+
+                distributed_amount = sc
+                    .get_distributed_rewards(managed_address!(&setup.user_address))
+                    .to_u64()
+                    .unwrap();
+                assert!(distributed_amount > 0);
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_RIDE_TOKENS - farm_in_amount + distributed_amount),
+    );
+
+    // nothing new to distribute in the same block
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let total_distributed = sc.distribute_rewards(10);
+                assert_eq!(total_distributed, managed_biguint!(0));
+            },
+        )
+        .assert_ok();
+
+    // distributed rewards are not paid again on claim
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (_, rewards_payment) = sc.claim_rewards().into_tuple();
+                assert_eq!(rewards_payment.amount, managed_biguint!(0));
+
+                assert_eq!(
+                    sc.get_distributed_rewards(managed_address!(&setup.user_address)),
+                    managed_biguint!(0)
+                );
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_RIDE_TOKENS - farm_in_amount + distributed_amount),
+    );
+
+    // new rewards accrue normally after the claim
+    setup.set_block_nonce(20);
+
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let total_distributed = sc.distribute_rewards(10);
+                assert!(total_distributed > 0);
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn distributed_rewards_kept_until_settled_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let keeper = setup.b_mock.create_user_account(&rust_biguint!(0));
+    let user_address = setup.user_address.clone();
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup.set_block_nonce(10);
+    setup.set_block_epoch(5);
+
+    let mut distributed_amount = 0;
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let total_distributed = sc.distribute_rewards(10);
+                distributed_amount = total_distributed.to_u64().unwrap();
+                assert!(distributed_amount > 0);
+            },
+        )
+        .assert_ok();
+
+    // a larger position, untracked as if it was created before the member list existed
+    let untracked_amount = 2 * farm_in_amount;
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(untracked_amount),
+            |sc| {
+                let _ = sc.stake_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_tx(
+            &user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let attributes: StakingFarmTokenAttributes<DebugApi> =
+                    sc.farm_token().get_token_attributes(3);
+                sc.member_rewards_info(&managed_address!(&user_address))
+                    .update(|info| {
+                        info.farm_amount -= managed_biguint!(untracked_amount);
+                        info.rps_weight -=
+                            managed_biguint!(untracked_amount) * attributes.reward_per_share;
+                    });
+            },
+        )
+        .assert_ok();
+
+    // exiting the untracked position keeps the rewards distributed for the tracked one
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            3,
+            &rust_biguint!(untracked_amount),
+            |sc| {
+                let (_, rewards_payment) = sc.unstake_farm().into_tuple();
+                assert_eq!(rewards_payment.amount, managed_biguint!(0));
+
+                assert_eq!(
+                    sc.get_distributed_rewards(managed_address!(&user_address)),
+                    managed_biguint!(distributed_amount)
+                );
+                assert!(sc.members().contains(&managed_address!(&user_address)));
+            },
+        )
+        .assert_ok();
+
+    // the remaining position is tracked again
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut members = MultiValueEncoded::new();
+                members.push(managed_address!(&user_address));
+                sc.sync_member_rewards(members);
+
+                let info = sc
+                    .member_rewards_info(&managed_address!(&user_address))
+                    .get();
+                assert_eq!(info.farm_amount, managed_biguint!(farm_in_amount));
+                assert_eq!(
+                    info.distributed_rewards,
+                    managed_biguint!(distributed_amount)
+                );
+            },
+        )
+        .assert_ok();
+
+    // distributed rewards are not paid again on claim
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (_, rewards_payment) = sc.claim_rewards().into_tuple();
+                assert_eq!(rewards_payment.amount, managed_biguint!(0));
+
+                assert_eq!(
+                    sc.get_distributed_rewards(managed_address!(&user_address)),
+                    managed_biguint!(0)
+                );
+            },
+        )
+        .assert_ok();
+
+    setup.set_block_nonce(20);
+
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let total_distributed = sc.distribute_rewards(10);
+                assert!(total_distributed > 0);
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn stake_farm_with_swap_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+    let pair_address = setup.second_farm_wrapper.address_ref().clone();
+    let user_address = setup.user_address.clone();
+
+    let other_token_id = b"OTHER-123456";
+    setup
+        .b_mock
+        .set_esdt_balance(&user_address, other_token_id, &rust_biguint!(1_000));
+
+    // no pair set for the token
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            other_token_id,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.stake_farm_with_swap(managed_biguint!(1));
+            },
+        )
+        .assert_user_error("No swap pair for token");
+
+    // pair must be a SC
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_swap_pair(
+                    managed_token_id!(other_token_id),
+                    managed_address!(&user_address),
+                );
+            },
+        )
+        .assert_user_error("Invalid pair address");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_swap_pair(
+                    managed_token_id!(other_token_id),
+                    managed_address!(&pair_address),
+                );
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.config_wrapper, |sc| {
+            assert_eq!(
+                sc.get_swap_pair(managed_token_id!(other_token_id)),
+                managed_address!(&pair_address)
+            );
+            assert_eq!(
+                sc.get_swap_pair(managed_token_id!(b"NONE-123456")),
+                managed_address!(&Address::zero())
+            );
+        })
+        .assert_ok();
+
+    // farming tokens are staked without a swap
+    let farm_in_amount = 100_000_000;
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_with_swap(managed_biguint!(farm_in_amount + 1));
+            },
+        )
+        .assert_user_error("Slippage exceeded");
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let new_farm_token = sc.stake_farm_with_swap(managed_biguint!(farm_in_amount));
+                assert_eq!(new_farm_token.token_nonce, 2);
+                assert_eq!(new_farm_token.amount, managed_biguint!(farm_in_amount));
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance::<Empty>(
+        &user_address,
+        FARM_TOKEN_ID,
+        2,
+        &rust_biguint!(farm_in_amount),
+        None,
+    );
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.remove_swap_pair(managed_token_id!(other_token_id));
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn unbond_and_claim_with_swap_no_pair_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let user_address = setup.user_address.clone();
+    let output_token_id = b"USDC-123456";
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup.set_block_epoch(5);
+    setup.set_block_nonce(10);
+
+    // no pair set for the output token
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.claim_rewards_and_swap(
+                    managed_token_id!(output_token_id),
+                    managed_biguint!(1),
+                );
+            },
+        )
+        .assert_user_error("No swap pair for token");
+
+    let expected_rewards = 40;
+    let expected_ride_token_balance =
+        rust_biguint!(USER_TOTAL_RIDE_TOKENS) - farm_in_amount + expected_rewards;
+    setup.unstake_farm(
+        farm_in_amount,
+        2,
+        expected_rewards,
+        &expected_ride_token_balance,
+        &expected_ride_token_balance,
+        1,
+        farm_in_amount,
+    );
+
+    setup.set_block_epoch(5 + MIN_UNBOND_EPOCHS);
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            UNBOND_TOKEN_ID,
+            1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc
+                    .unbond_farm_and_swap(managed_token_id!(output_token_id), managed_biguint!(1));
+            },
+        )
+        .assert_user_error("No swap pair for token");
+
+    // unbond tokens are kept, and can still be redeemed normally
+    setup.unbond_farm(
+        1,
+        farm_in_amount,
+        farm_in_amount,
+        USER_TOTAL_RIDE_TOKENS + expected_rewards,
+    );
+}
+
+#[test]
+fn expired_boosted_rewards_recycled_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+    let user_address = setup.user_address.clone();
+
+    // without an energy factory, the user never reaches the min energy
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_boosted_yields_factors(
+                    managed_biguint!(2),
+                    managed_biguint!(1),
+                    managed_biguint!(1),
+                    managed_biguint!(1),
+                    managed_biguint!(1),
+                );
+                sc.set_boosted_yields_rewards_percentage(MAX_PERCENT / 2);
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup.set_block_nonce(10);
+    setup.set_block_epoch(5);
+
+    let expected_rewards = 40;
+    let expected_week_rewards = expected_rewards / 2;
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (_, rewards_payment) = sc.claim_rewards().into_tuple();
+                assert_eq!(
+                    rewards_payment.amount,
+                    managed_biguint!(expected_week_rewards)
+                );
+            },
+        )
+        .assert_ok();
+
+    // week 1 may still be claimed in week 5
+    setup.set_block_epoch(4 * EPOCHS_IN_WEEK);
+    setup
+        .b_mock
+        .execute_tx(
+            &user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.update_energy_for_user(managed_address!(&user_address));
+
+                assert_eq!(sc.get_current_week(), 5);
+                assert_eq!(
+                    sc.accumulated_rewards_for_week(1).get(),
+                    managed_biguint!(expected_week_rewards)
+                );
+                assert_eq!(sc.claimed_rewards_for_week(1).get(), managed_biguint!(0));
+            },
+        )
+        .assert_ok();
+
+    // week 1 expired, so its rewards are added to the current week
+    setup.set_block_epoch(5 * EPOCHS_IN_WEEK);
+    setup
+        .b_mock
+        .execute_tx(
+            &user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.update_energy_for_user(managed_address!(&user_address));
+
+                assert_eq!(sc.get_current_week(), 6);
+                assert_eq!(
+                    sc.accumulated_rewards_for_week(1).get(),
+                    managed_biguint!(0)
+                );
+                assert_eq!(
+                    sc.accumulated_rewards_for_week(6).get(),
+                    managed_biguint!(expected_week_rewards)
+                );
+                assert_eq!(
+                    sc.get_unclaimed_boosted_rewards(managed_address!(&user_address)),
+                    managed_biguint!(0)
+                );
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn claim_rewards_and_swap_no_rewards_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let user_address = setup.user_address.clone();
+    let output_token_id = b"USDC-123456";
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    // nothing to swap in the same block, so no swap pair is needed either
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (new_farm_token, rewards_payment) = sc
+                    .claim_rewards_and_swap(managed_token_id!(output_token_id), managed_biguint!(1))
+                    .into_tuple();
+                assert_eq!(new_farm_token.token_nonce, 3);
+                assert_eq!(new_farm_token.amount, managed_biguint!(farm_in_amount));
+
+                assert_eq!(
+                    rewards_payment.token_identifier,
+                    managed_token_id!(output_token_id)
+                );
+                assert_eq!(rewards_payment.amount, managed_biguint!(0));
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance::<Empty>(
+        &user_address,
+        FARM_TOKEN_ID,
+        3,
+        &rust_biguint!(farm_in_amount),
+        None,
+    );
+    setup
+        .b_mock
+        .check_esdt_balance(&user_address, output_token_id, &rust_biguint!(0));
+}
+
+#[test]
+fn boosted_yields_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+    let user_address = setup.user_address.clone();
+
+    // energy factory stand-in
+    let energy_factory_wrapper = setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        Some(&factory_address),
+        energy_factory::contract_obj,
+        "energy factory",
+    );
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &energy_factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.user_energy(&managed_address!(&user_address))
+                    .set(Energy::new(
+                        BigInt::from(managed_biguint!(1_000)),
+                        0,
+                        managed_biguint!(0),
+                    ));
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_boosted_yields_rewards_percentage(MAX_PERCENT + 1);
+            },
+        )
+        .assert_user_error("Invalid percentage");
+
+    // rewards may not be kept for boosted yields before the factors are set
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_boosted_yields_rewards_percentage(MAX_PERCENT / 2);
+            },
+        )
+        .assert_user_error("Boosted yields factors not set");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_energy_factory_address(managed_address!(
+                    energy_factory_wrapper.address_ref()
+                ));
+                sc.set_boosted_yields_factors(
+                    managed_biguint!(2),
+                    managed_biguint!(1),
+                    managed_biguint!(1),
+                    managed_biguint!(1),
+                    managed_biguint!(1),
+                );
+                sc.set_boosted_yields_rewards_percentage(MAX_PERCENT / 2);
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            let progress = sc
+                .user_boosted_yields_progress(&managed_address!(&user_address))
+                .get();
+            assert_eq!(progress.week, 1);
+            assert_eq!(progress.energy, managed_biguint!(1_000));
+            assert_eq!(progress.farm_amount, managed_biguint!(farm_in_amount));
+        })
+        .assert_ok();
+
+    setup.set_block_nonce(10);
+    setup.set_block_epoch(5);
+
+    // half of the user rewards are kept for boosted yields
+    let expected_rewards = 40;
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (_, rewards_payment) = sc.claim_rewards().into_tuple();
+                assert_eq!(
+                    rewards_payment.amount,
+                    managed_biguint!(expected_rewards / 2)
+                );
+
+                assert_eq!(
+                    sc.accumulated_rewards_for_week(1).get(),
+                    managed_biguint!(expected_rewards / 2)
+                );
+            },
+        )
+        .assert_ok();
+
+    // week not over yet
+    setup
+        .b_mock
+        .execute_tx(
+            &user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let payment = sc.claim_boosted_rewards();
+                assert_eq!(payment.amount, managed_biguint!(0));
+            },
+        )
+        .assert_ok();
+
+    setup.set_block_epoch(8);
+
+    // only staker, so all the rewards of the first week go to the user
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            assert_eq!(sc.get_current_week(), 2);
+            assert_eq!(
+                sc.get_unclaimed_boosted_rewards(managed_address!(&user_address)),
+                managed_biguint!(expected_rewards / 2)
+            );
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let payment = sc.claim_boosted_rewards();
+                assert_eq!(payment.amount, managed_biguint!(expected_rewards / 2));
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_esdt_balance(
+        &user_address,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_RIDE_TOKENS - farm_in_amount + expected_rewards),
+    );
+
+    // rewards may only be claimed once
+    setup
+        .b_mock
+        .execute_tx(
+            &user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let payment = sc.claim_boosted_rewards();
+                assert_eq!(payment.amount, managed_biguint!(0));
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn switch_guild_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 2;
+    setup.stake_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0);
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_guild_switch_cooldown_epochs(10);
+            },
+        )
+        .assert_ok();
+
+    setup.set_block_epoch(5);
+    setup.set_block_nonce(10);
+
+    let mut total_staked_before = 0u64;
+    setup
+        .b_mock
+        .execute_query(&setup.config_wrapper, |sc| {
+            total_staked_before = sc.total_staking_token_staked().get().to_u64().unwrap();
+        })
+        .assert_ok();
+
+    // user switches to the second guild, receiving the rewards
+    let other_guild_addr = setup.second_farm_wrapper.address_ref().clone();
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                sc.switch_guild(managed_address!(&other_guild_addr));
+            },
+        )
+        .assert_ok();
+
+    let expected_rewards = 40;
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        REWARD_TOKEN_ID,
+        &(rust_biguint!(USER_TOTAL_RIDE_TOKENS) - farm_in_amount + expected_rewards),
+    );
+    setup.b_mock.check_nft_balance::<Empty>(
+        &setup.user_address,
+        OTHER_FARM_TOKEN_ID,
+        2,
+        &rust_biguint!(farm_in_amount),
+        None,
+    );
+
+    setup
+        .b_mock
+        .execute_query(&setup.config_wrapper, |sc| {
+            assert_eq!(
+                sc.total_staking_token_staked().get(),
+                managed_biguint!(total_staked_before)
+            );
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.second_farm_wrapper, |sc| {
+            assert_eq!(
+                sc.user_tokens(&managed_address!(&setup.user_address)).get(),
+                managed_biguint!(farm_in_amount)
+            );
+        })
+        .assert_ok();
+
+    // switching back is not allowed before the cooldown is over
+    let first_guild_addr = setup.first_farm_wrapper.address_ref().clone();
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.second_farm_wrapper,
+            OTHER_FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                sc.switch_guild(managed_address!(&first_guild_addr));
+            },
+        )
+        .assert_user_error("Switch cooldown not over");
+
+    setup.set_block_epoch(15);
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.second_farm_wrapper,
+            OTHER_FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                sc.switch_guild(managed_address!(&first_guild_addr));
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance::<Empty>(
+        &setup.user_address,
+        FARM_TOKEN_ID,
+        3,
+        &rust_biguint!(farm_in_amount),
+        None,
+    );
+}
+
+#[test]
+fn migrate_closed_guild_members_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let user_address = setup.user_address.clone();
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 2;
+    setup.stake_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0);
+
+    // position created before the member list existed
+    setup
+        .b_mock
+        .execute_tx(
+            &user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.member_rewards_info(&managed_address!(&user_address))
+                    .clear();
+                let _ = sc.members().swap_remove(&managed_address!(&user_address));
+            },
+        )
+        .assert_ok();
+
+    setup.set_block_epoch(5);
+    setup.set_block_nonce(10);
+
+    // close guild
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(1),
+            |sc| {
+                sc.close_guild();
+            },
+        )
+        .assert_ok();
+
+    // keeper migrates members without a default guild
+    let closed_guild_addr = setup.first_farm_wrapper.address_ref().clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.second_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.migrate_closed_guild_members(
+                    managed_address!(&closed_guild_addr),
+                    10,
+                    OptionalValue::None,
+                );
+            },
+        )
+        .assert_user_error("No default migration guild");
+
+    let other_guild_addr = setup.second_farm_wrapper.address_ref().clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_default_migration_guild(managed_address!(&other_guild_addr));
+            },
+        )
+        .assert_ok();
+
+    // untracked members are not migrated
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.second_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let members_count = sc.migrate_closed_guild_members(
+                    managed_address!(&closed_guild_addr),
+                    10,
+                    OptionalValue::None,
+                );
+                assert_eq!(members_count, 0);
+            },
+        )
+        .assert_ok();
+
+    // anyone may track them, also while the guild is closing
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.second_owner_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut members = MultiValueEncoded::new();
+                members.push(managed_address!(&user_address));
+                sc.sync_member_rewards(members);
+
+                assert!(sc.members().contains(&managed_address!(&user_address)));
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.second_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let members_count = sc.migrate_closed_guild_members(
+                    managed_address!(&closed_guild_addr),
+                    10,
+                    OptionalValue::None,
+                );
+                assert_eq!(members_count, 1);
+            },
+        )
+        .assert_ok();
+
+    // the stake moved to the other guild, while the rewards accrued before tracking are still pending
+    setup.b_mock.check_esdt_balance(
+        &user_address,
+        REWARD_TOKEN_ID,
+        &(rust_biguint!(USER_TOTAL_RIDE_TOKENS) - farm_in_amount),
+    );
+    setup.b_mock.check_nft_balance::<Empty>(
+        &user_address,
+        OTHER_FARM_TOKEN_ID,
+        2,
+        &rust_biguint!(farm_in_amount),
+        None,
+    );
+
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            assert!(!sc.members().contains(&managed_address!(&user_address)));
+            assert!(!sc
+                .migrated_positions(&managed_address!(&user_address))
+                .is_empty());
+        })
+        .assert_ok();
+
+    // old farm tokens may not be used anymore
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.unstake_farm();
+            },
+        )
+        .assert_user_error("Position was migrated to another guild");
+
+    // except for claiming their remaining rewards
+    let expected_rewards = 40;
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let rewards_payment = sc.claim_migrated_rewards();
+                assert_eq!(rewards_payment.amount, managed_biguint!(expected_rewards));
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_esdt_balance(
+        &user_address,
+        REWARD_TOKEN_ID,
+        &(rust_biguint!(USER_TOTAL_RIDE_TOKENS) - farm_in_amount + expected_rewards),
+    );
+    setup.b_mock.check_nft_balance::<Empty>(
+        &user_address,
+        FARM_TOKEN_ID,
+        expected_farm_token_nonce,
+        &rust_biguint!(0),
+        None,
+    );
+}
+
+#[test]
+fn migrate_closed_guild_members_skip_failed_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let user_address = setup.user_address.clone();
+    let other_guild_master = setup.second_owner_address.clone();
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    // the guild master of the destination guild may not be received as a member
+    setup.b_mock.set_esdt_balance(
+        &other_guild_master,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(farm_in_amount),
+    );
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &other_guild_master,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    setup.set_block_epoch(5);
+    setup.set_block_nonce(10);
+
+    let mut compounded_amount = 0u64;
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let new_farm_token = sc.compound_rewards();
+                compounded_amount = new_farm_token.amount.to_u64().unwrap() - farm_in_amount;
+                assert!(compounded_amount > 0);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(1),
+            |sc| {
+                sc.close_guild();
+            },
+        )
+        .assert_ok();
+
+    let closed_guild_addr = setup.first_farm_wrapper.address_ref().clone();
+    let other_guild_addr = setup.second_farm_wrapper.address_ref().clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let members_count = sc.migrate_closed_guild_members(
+                    managed_address!(&closed_guild_addr),
+                    10,
+                    OptionalValue::Some(managed_address!(&other_guild_addr)),
+                );
+                assert_eq!(members_count, 1);
+
+                assert_eq!(
+                    sc.unmigrated_tokens(&managed_address!(&other_guild_master))
+                        .get(),
+                    managed_biguint!(farm_in_amount)
+                );
+            },
+        )
+        .assert_ok();
+
+    // compounded rewards are kept in the new position
+    let migrated_amount = farm_in_amount + compounded_amount;
+    setup.b_mock.check_nft_balance::<Empty>(
+        &user_address,
+        OTHER_FARM_TOKEN_ID,
+        2,
+        &rust_biguint!(migrated_amount),
+        None,
+    );
+    setup
+        .b_mock
+        .execute_query(&setup.second_farm_wrapper, |sc| {
+            let attributes: StakingFarmTokenAttributes<DebugApi> =
+                sc.farm_token().get_token_attributes(2);
+            assert_eq!(
+                attributes.compounded_reward,
+                managed_biguint!(compounded_amount)
+            );
+            assert_eq!(
+                attributes.current_farm_amount,
+                managed_biguint!(migrated_amount)
+            );
+        })
+        .assert_ok();
+
+    // skipped members get their stake back from the factory
+    setup
+        .b_mock
+        .execute_tx(
+            &other_guild_master,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let payment = sc.claim_unmigrated_tokens();
+                assert_eq!(payment.amount, managed_biguint!(farm_in_amount));
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_esdt_balance(
+        &other_guild_master,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(farm_in_amount),
+    );
+
+    setup
+        .b_mock
+        .execute_tx(
+            &other_guild_master,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.claim_unmigrated_tokens();
+            },
+        )
+        .assert_user_error("No unmigrated tokens");
+}
+
+#[test]
+fn closed_guild_exit_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 2;
+    setup.stake_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0);
+
+    setup.set_block_epoch(5);
+    setup.set_block_nonce(10);
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(1),
+            |sc| {
+                sc.close_guild();
+            },
+        )
+        .assert_ok();
+
+    // unlocked unbond token, which may be redeemed right away
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_closed_guild_exit_mode(ClosedGuildExitMode::UnlockedUnbondToken);
+            },
+        )
+        .assert_ok();
+
+    let half_amount = farm_in_amount / 2;
+    let mut rewards_amount = 0u64;
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(half_amount),
+            |sc| {
+                let (unbond_token, rewards_payment) = sc.unstake_farm().into_tuple();
+                rewards_amount = rewards_payment.amount.to_u64().unwrap();
+                assert_eq!(
+                    unbond_token.token_identifier,
+                    managed_token_id!(UNBOND_TOKEN_ID)
+                );
+                assert_eq!(unbond_token.amount, managed_biguint!(half_amount));
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            UNBOND_TOKEN_ID,
+            2,
+            &rust_biguint!(half_amount),
+            |sc| {
+                let _ = sc.unbond_farm();
+            },
+        )
+        .assert_ok();
+
+    // direct payout of the farming tokens
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_closed_guild_exit_mode(ClosedGuildExitMode::DirectPayout);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(half_amount),
+            |sc| {
+                let (farming_tokens, rewards_payment) = sc.unstake_farm().into_tuple();
+                rewards_amount += rewards_payment.amount.to_u64().unwrap();
+                assert_eq!(
+                    farming_tokens.token_identifier,
+                    managed_token_id!(FARMING_TOKEN_ID)
+                );
+                assert_eq!(farming_tokens.amount, managed_biguint!(half_amount));
+            },
+        )
+        .assert_ok();
+
+    // ~= 40, rounded down for each half
+    assert!(rewards_amount > 0 && rewards_amount <= 40);
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        FARMING_TOKEN_ID,
+        &(rust_biguint!(USER_TOTAL_RIDE_TOKENS) + rewards_amount),
+    );
+}
+
+#[test]
+fn batch_cancel_unbond_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let user_address = setup.user_address.clone();
+
+    let farm_in_amount = 10_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+    setup.stake_farm(farm_in_amount, &[], 3, 0, 0);
+    setup.stake_farm(farm_in_amount, &[], 4, 0, 0);
+
+    for farm_token_nonce in [2, 3] {
+        setup
+            .b_mock
+            .execute_esdt_transfer(
+                &user_address,
+                &setup.first_farm_wrapper,
+                FARM_TOKEN_ID,
+                farm_token_nonce,
+                &rust_biguint!(farm_in_amount),
+                |sc| {
+                    let _ = sc.unstake_farm();
+                },
+            )
+            .assert_ok();
+    }
+
+    // both unbond tokens are restored, and merged with the remaining farm token
+    let payments = [
+        TxTokenTransfer {
+            token_identifier: UNBOND_TOKEN_ID.to_vec(),
+            nonce: 1,
+            value: rust_biguint!(farm_in_amount),
+        },
+        TxTokenTransfer {
+            token_identifier: UNBOND_TOKEN_ID.to_vec(),
+            nonce: 2,
+            value: rust_biguint!(farm_in_amount),
+        },
+        TxTokenTransfer {
+            token_identifier: FARM_TOKEN_ID.to_vec(),
+            nonce: 4,
+            value: rust_biguint!(farm_in_amount),
+        },
+    ];
+    let total_amount = farm_in_amount * 3;
+    setup
+        .b_mock
+        .execute_esdt_multi_transfer(&user_address, &setup.first_farm_wrapper, &payments, |sc| {
+            let new_farm_token = sc.cancel_unbond();
+            assert_eq!(new_farm_token.token_nonce, 5);
+            assert_eq!(new_farm_token.amount, managed_biguint!(total_amount));
+
+            let user_tokens = sc.user_tokens(&managed_address!(&user_address)).get();
+            assert_eq!(user_tokens, managed_biguint!(total_amount));
+        })
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance(
+        &user_address,
+        FARM_TOKEN_ID,
+        5,
+        &rust_biguint!(total_amount),
+        Some(&StakingFarmTokenAttributes::<DebugApi> {
+            reward_per_share: managed_biguint!(0),
+            compounded_reward: managed_biguint!(0),
+            current_farm_amount: managed_biguint!(total_amount),
+        }),
+    );
+
+    // restake the unbond token in the second guild
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            5,
+            &rust_biguint!(total_amount),
+            |sc| {
+                let _ = sc.unstake_farm();
+            },
+        )
+        .assert_ok();
+
+    let other_guild_addr = setup.second_farm_wrapper.address_ref().clone();
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            UNBOND_TOKEN_ID,
+            3,
+            &rust_biguint!(total_amount),
+            |sc| {
+                let new_farm_token = sc.cancel_unbond_to_guild(managed_address!(&other_guild_addr));
+                assert_eq!(
+                    new_farm_token.token_identifier,
+                    managed_token_id!(OTHER_FARM_TOKEN_ID)
+                );
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance::<Empty>(
+        &user_address,
+        OTHER_FARM_TOKEN_ID,
+        2,
+        &rust_biguint!(total_amount),
+        None,
+    );
+}
+
+#[test]
+fn reopen_guild_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+
+    let current_epoch = 5;
+    setup.set_block_epoch(current_epoch);
+    setup.set_block_nonce(10);
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(1),
+            |sc| {
+                sc.close_guild();
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.factory_wrapper, |sc| {
+            assert_eq!(sc.get_current_active_guilds(), 1);
+        })
+        .assert_ok();
+
+    // too late to reopen
+    setup.set_block_epoch(current_epoch + MIN_UNBOND_EPOCHS);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            UNBOND_TOKEN_ID,
+            1,
+            &rust_biguint!(1),
+            |sc| {
+                let _ = sc.reopen_guild();
+            },
+        )
+        .assert_user_error("Unbond period over");
+
+    setup.set_block_epoch(current_epoch + 1);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            UNBOND_TOKEN_ID,
+            1,
+            &rust_biguint!(1),
+            |sc| {
+                let new_farm_token = sc.reopen_guild();
+                assert_eq!(new_farm_token.token_nonce, 2);
+                assert_eq!(new_farm_token.amount, managed_biguint!(1));
+
+                assert!(!sc.guild_closing().get());
+                assert_eq!(sc.guild_master_tokens().get(), managed_biguint!(1));
+            },
+        )
+        .assert_ok();
+
+    let first_guild_addr = setup.first_farm_wrapper.address_ref().clone();
+    setup
+        .b_mock
+        .execute_query(&setup.factory_wrapper, |sc| {
+            assert_eq!(sc.get_current_active_guilds(), 2);
+            assert!(!sc
+                .closed_guilds()
+                .contains(&managed_address!(&first_guild_addr)));
+        })
+        .assert_ok();
+
+    // users may stake again
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000_000),
+            |sc| {
+                let _ = sc.stake_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn reopen_guild_after_migration_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let user_address = setup.user_address.clone();
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 2;
+    setup.stake_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0);
+
+    let current_epoch = 5;
+    setup.set_block_epoch(current_epoch);
+    setup.set_block_nonce(10);
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(1),
+            |sc| {
+                sc.close_guild();
+            },
+        )
+        .assert_ok();
+
+    let closed_guild_addr = setup.first_farm_wrapper.address_ref().clone();
+    let other_guild_addr = setup.second_farm_wrapper.address_ref().clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_default_migration_guild(managed_address!(&other_guild_addr));
+
+                let members_count = sc.migrate_closed_guild_members(
+                    managed_address!(&closed_guild_addr),
+                    10,
+                    OptionalValue::None,
+                );
+                assert_eq!(members_count, 1);
+            },
+        )
+        .assert_ok();
+
+    setup.set_block_epoch(current_epoch + 1);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            UNBOND_TOKEN_ID,
+            1,
+            &rust_biguint!(1),
+            |sc| {
+                let _ = sc.reopen_guild();
+            },
+        )
+        .assert_ok();
+
+    // the migrated member may join the reopened guild again
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let new_farm_token = sc.stake_farm_endpoint(OptionalValue::None);
+                assert!(new_farm_token.token_nonce > expected_farm_token_nonce);
+
+                assert!(sc.members().contains(&managed_address!(&user_address)));
+            },
+        )
+        .assert_ok();
+
+    // while the farm tokens created before the migration are still rejected
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.unstake_farm();
+            },
+        )
+        .assert_user_error("Position was migrated to another guild");
+}
+
+#[test]
+fn slash_guild_master_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let first_guild_addr = setup.first_farm_wrapper.address_ref().clone();
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    let guild_master_stake = 1_000;
+    setup.b_mock.set_esdt_balance(
+        &setup.first_owner_address,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(guild_master_stake),
+    );
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(guild_master_stake),
+            |sc| {
+                let _ = sc.stake_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    // only admins may slash
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.slash_guild_master(managed_address!(&first_guild_addr), 1_000, false);
+            },
+        )
+        .assert_user_error("Endpoint can only be called by admins");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.slash_guild_master(managed_address!(&first_guild_addr), 2_001, false);
+            },
+        )
+        .assert_user_error("Invalid slash percentage");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.slash_guild_master(managed_address!(&first_guild_addr), 1_000, true);
+            },
+        )
+        .assert_user_error("No slashing treasury");
+
+    // 10% of 1_001 tokens, distributed to the only member
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let slashed_amount =
+                    sc.slash_guild_master(managed_address!(&first_guild_addr), 1_000, false);
+                assert_eq!(slashed_amount, managed_biguint!(100));
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            assert_eq!(sc.guild_master_tokens().get(), managed_biguint!(901));
+            assert_eq!(
+                sc.slashed_guild_master_tokens().get(),
+                managed_biguint!(100)
+            );
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (_, rewards_payment) = sc.claim_rewards().into_tuple();
+                assert_eq!(rewards_payment.amount, managed_biguint!(100));
+            },
+        )
+        .assert_ok();
+
+    // 10% of the remaining 901 tokens, sent to the treasury
+    let treasury_address = setup.b_mock.create_user_account(&rust_biguint!(0));
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_slashing_treasury(managed_address!(&treasury_address));
+
+                let slashed_amount =
+                    sc.slash_guild_master(managed_address!(&first_guild_addr), 1_000, true);
+                assert_eq!(slashed_amount, managed_biguint!(90));
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .check_esdt_balance(&treasury_address, FARMING_TOKEN_ID, &rust_biguint!(90));
+
+    // slashed tokens are deducted when the guild master unstakes
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            3,
+            &rust_biguint!(guild_master_stake),
+            |sc| {
+                let (unbond_token, _) = sc.unstake_farm().into_tuple();
+                assert_eq!(unbond_token.amount, managed_biguint!(810));
+
+                assert_eq!(sc.guild_master_tokens().get(), managed_biguint!(1));
+                assert_eq!(sc.slashed_guild_master_tokens().get(), managed_biguint!(0));
+            },
+        )
+        .assert_ok();
+
+    // cancelling the unbond does not restore the slashed tokens
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            UNBOND_TOKEN_ID,
+            1,
+            &rust_biguint!(810),
+            |sc| {
+                let new_farm_token = sc.cancel_unbond();
+                assert_eq!(new_farm_token.amount, managed_biguint!(810));
+
+                assert_eq!(sc.guild_master_tokens().get(), managed_biguint!(811));
+                assert_eq!(sc.slashed_guild_master_tokens().get(), managed_biguint!(0));
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn replace_guild_master_proposal_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let first_guild_addr = setup.first_farm_wrapper.address_ref().clone();
+    let new_guild_master = setup.b_mock.create_user_account(&rust_biguint!(0));
+
+    let current_epoch = 5;
+    setup.set_block_epoch(current_epoch);
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    // guild master is not a member
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.create_proposal(ProposalAction::ReplaceGuildMaster(managed_address!(
+                    &new_guild_master
+                )));
+            },
+        )
+        .assert_user_error("Only members may create proposals");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let proposal_id = sc.create_proposal(ProposalAction::ReplaceGuildMaster(
+                    managed_address!(&new_guild_master),
+                ));
+                assert_eq!(proposal_id, 1);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.create_proposal(ProposalAction::PauseGuild);
+            },
+        )
+        .assert_user_error("Proposer already has an active proposal");
+
+    // others may not block the election by staking on behalf of the candidate
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_for(managed_address!(&new_guild_master));
+            },
+        )
+        .assert_user_error("May not stake on behalf of a guild master candidate");
+
+    // members with less than 1% of the stake may not create proposals
+    let small_member = setup.b_mock.create_user_account(&rust_biguint!(0));
+    setup
+        .b_mock
+        .set_esdt_balance(&small_member, FARMING_TOKEN_ID, &rust_biguint!(1));
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &small_member,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(1),
+            |sc| {
+                let _ = sc.stake_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &small_member,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.create_proposal(ProposalAction::PauseGuild);
+            },
+        )
+        .assert_user_error("Not enough stake to create proposals");
+
+    // other members may create their own proposals
+    let other_member = setup.second_owner_address.clone();
+    setup.b_mock.set_esdt_balance(
+        &other_member,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(farm_in_amount),
+    );
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &other_member,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &other_member,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let proposal_id = sc.create_proposal(ProposalAction::PauseGuild);
+                assert_eq!(proposal_id, 2);
+                assert_eq!(sc.active_proposals().len(), 2);
+            },
+        )
+        .assert_ok();
+
+    // tokens staked after the proposal was created do not count
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.vote(1, true);
+
+                assert_eq!(
+                    sc.get_voting_power(1, managed_address!(&setup.user_address)),
+                    managed_biguint!(farm_in_amount)
+                );
+                assert_eq!(
+                    sc.proposal(1).get().votes_for,
+                    managed_biguint!(farm_in_amount)
+                );
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.vote(1, true);
+            },
+        )
+        .assert_user_error("Already voted");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.execute_proposal(1);
+            },
+        )
+        .assert_user_error("Voting period not over");
+
+    // the guild master joined before the member list existed
+    let guild_master_address = setup.first_owner_address.clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &guild_master_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.member_rewards_info(&managed_address!(&guild_master_address))
+                    .clear();
+                let _ = sc
+                    .members()
+                    .swap_remove(&managed_address!(&guild_master_address));
+            },
+        )
+        .assert_ok();
+
+    // suspended guilds may still replace their guild master
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.suspend_guild(managed_address!(&first_guild_addr), 1);
+            },
+        )
+        .assert_ok();
+
+    setup.set_block_epoch(current_epoch + 7);
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let status = sc.execute_proposal(1);
+                assert!(status == ProposalStatus::Executed);
+
+                assert!(!sc.active_proposals().contains(&1));
+                assert!(sc.active_proposals().contains(&2));
+                assert_eq!(
+                    sc.guild_master_candidate_proposals(&managed_address!(&new_guild_master))
+                        .get(),
+                    0
+                );
+
+                assert_eq!(
+                    sc.guild_master_address().get(),
+                    managed_address!(&new_guild_master)
+                );
+                assert!(sc.guild_master_tokens().is_empty());
+                assert!(!sc
+                    .migrated_positions(&managed_address!(&setup.first_owner_address))
+                    .is_empty());
+            },
+        )
+        .assert_ok();
+
+    // the stake of the old guild master is unbonded
+    setup.b_mock.check_nft_balance::<Empty>(
+        &setup.first_owner_address,
+        UNBOND_TOKEN_ID,
+        1,
+        &rust_biguint!(1),
+        None,
+    );
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(1),
+            |sc| {
+                let _ = sc.claim_rewards();
+            },
+        )
+        .assert_user_error("Position was migrated to another guild");
+
+    // the old farm tokens may still be sent for their remaining rewards
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(1),
+            |sc| {
+                let _ = sc.claim_migrated_rewards();
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance::<Empty>(
+        &setup.first_owner_address,
+        FARM_TOKEN_ID,
+        1,
+        &rust_biguint!(0),
+        None,
+    );
+
+    setup
+        .b_mock
+        .execute_query(&setup.factory_wrapper, |sc| {
+            let guild_id = sc.guild_ids().get_id(&managed_address!(&first_guild_addr));
+            let new_guild_master_id = sc.user_ids().get_id(&managed_address!(&new_guild_master));
+            assert_eq!(
+                sc.guild_master_for_guild(guild_id).get(),
+                new_guild_master_id
+            );
+            assert_eq!(sc.guild_sc_for_user(new_guild_master_id).get(), guild_id);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn pause_guild_proposal_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+
+    let current_epoch = 5;
+    setup.set_block_epoch(current_epoch);
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let proposal_id = sc.create_proposal(ProposalAction::PauseGuild);
+                sc.vote(proposal_id, true);
+            },
+        )
+        .assert_ok();
+
+    setup.set_block_epoch(current_epoch + 7);
+    setup.set_block_nonce(10);
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let status = sc.execute_proposal(1);
+                assert!(status == ProposalStatus::Executed);
+                assert!(sc.guild_paused().get());
+            },
+        )
+        .assert_ok();
+
+    // the paused guild may not be entered, and its rewards may not be compounded
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_user_error("Guild paused");
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.compound_rewards();
+            },
+        )
+        .assert_user_error("Guild paused");
+
+    // while members may still claim and exit
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.claim_rewards();
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            3,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.unstake_farm();
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn officers_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_officer(managed_address!(&setup.user_address));
+            },
+        )
+        .assert_user_error("Only guild master may call this endpoint");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_officer(managed_address!(&setup.first_owner_address));
+            },
+        )
+        .assert_user_error("Invalid officer");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_officer(managed_address!(&setup.user_address));
+
+                let officer = managed_address!(&setup.user_address);
+                assert!(sc.officers().contains(&officer));
+                assert_eq!(sc.officer_tokens().get(), managed_biguint!(farm_in_amount));
+                assert_eq!(
+                    sc.get_min_stake_for_user(&officer),
+                    sc.get_min_stake_guild_master()
+                );
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_officer(managed_address!(&setup.user_address));
+            },
+        )
+        .assert_user_error("Already an officer");
+
+    // the only member is an officer, so all rewards go to the officer accumulator
+    setup.set_block_nonce(10);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (_, rewards_payment) = sc.claim_rewards().into_tuple();
+                assert!(rewards_payment.amount > 0);
+
+                assert!(sc.officer_rps().get() > 0);
+                assert_eq!(sc.user_rps().get(), managed_biguint!(0));
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.remove_officer(managed_address!(&setup.user_address));
+
+                let officer = managed_address!(&setup.user_address);
+                assert!(!sc.officers().contains(&officer));
+                assert_eq!(sc.officer_tokens().get(), managed_biguint!(0));
+
+                // the rps of the former officer continues from the officer rps
+                let checkpoint = sc.rps_checkpoint(&officer).get();
+                assert_eq!(checkpoint.base_rps, sc.officer_rps().get());
+                assert_eq!(checkpoint.class_rps, sc.user_rps().get());
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.remove_officer(managed_address!(&setup.user_address));
+            },
+        )
+        .assert_user_error("Not an officer");
+}
+
+#[test]
+fn officer_unstake_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+
+    let current_epoch = 5;
+    setup.set_block_epoch(current_epoch);
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_officer(managed_address!(&setup.user_address));
+                sc.set_user_unbond_epochs(10);
+
+                // officers do not unbond faster than the users of the guild
+                assert_eq!(sc.get_officer_unbond_epochs(), 10);
+            },
+        )
+        .assert_ok();
+
+    setup.set_block_nonce(10);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.unstake_farm();
+
+                // the officer without stake no longer takes an officer slot
+                let officer = managed_address!(&setup.user_address);
+                assert!(!sc.officers().contains(&officer));
+                assert_eq!(sc.officer_tokens().get(), managed_biguint!(0));
+            },
+        )
+        .assert_ok();
+
+    setup.set_block_epoch(current_epoch + MIN_UNBOND_EPOCHS);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            UNBOND_TOKEN_ID,
+            1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.unbond_farm();
+            },
+        )
+        .assert_user_error("Unbond period not over");
+
+    setup.set_block_epoch(current_epoch + 10);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            UNBOND_TOKEN_ID,
+            1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.unbond_farm();
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn sponsor_campaign_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let sponsor = setup.b_mock.create_user_account(&rust_biguint!(0));
+    let campaign_amount = 1_000_000;
+    setup
+        .b_mock
+        .set_esdt_balance(&sponsor, REWARD_TOKEN_ID, &rust_biguint!(campaign_amount));
+
+    let current_epoch = 5;
+    setup.set_block_epoch(current_epoch);
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &sponsor,
+            &setup.first_farm_wrapper,
+            REWARD_TOKEN_ID,
+            0,
+            &rust_biguint!(campaign_amount),
+            |sc| {
+                sc.start_sponsor_campaign(1_000, 0);
+            },
+        )
+        .assert_user_error("Invalid campaign duration");
+
+    // the budget must cover 10% APR on 100_000_000 tokens for 10 epochs
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &sponsor,
+            &setup.first_farm_wrapper,
+            REWARD_TOKEN_ID,
+            0,
+            &rust_biguint!(273_971),
+            |sc| {
+                sc.start_sponsor_campaign(1_000, 10);
+            },
+        )
+        .assert_user_error("Campaign budget too low");
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &sponsor,
+            &setup.first_farm_wrapper,
+            REWARD_TOKEN_ID,
+            0,
+            &rust_biguint!(campaign_amount),
+            |sc| {
+                sc.start_sponsor_campaign(1_000, 10);
+
+                let campaign = sc.sponsor_campaign().get();
+                assert_eq!(campaign.end_epoch, current_epoch + 10);
+                assert_eq!(
+                    sc.get_sponsor_campaign_remaining_rewards(),
+                    managed_biguint!(campaign_amount)
+                );
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &sponsor,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.end_sponsor_campaign();
+            },
+        )
+        .assert_user_error("Campaign not over");
+
+    // campaign rewards are added on top of the tier rewards,
+    // 10% APR on 100_000_000 tokens is about 1.9 tokens per block
+    setup.set_block_nonce(10);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.claim_rewards();
+
+                let campaign = sc.sponsor_campaign().get();
+                assert_eq!(campaign.end_block_nonce, 144_000);
+                assert_eq!(campaign.distributed, managed_biguint!(19));
+            },
+        )
+        .assert_ok();
+
+    // nobody interacts during the last epochs, which are still rewarded up to the end block
+    let campaign_rewards = 19 + 273_953;
+    setup.set_block_epoch(current_epoch + 10);
+    setup.set_block_nonce(145_000);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            3,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.claim_rewards();
+
+                assert_eq!(
+                    sc.sponsor_campaign().get().distributed,
+                    managed_biguint!(campaign_rewards)
+                );
+            },
+        )
+        .assert_ok();
+
+    // no campaign rewards are produced after the end block
+    setup.set_block_nonce(146_000);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            4,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.claim_rewards();
+
+                assert_eq!(
+                    sc.sponsor_campaign().get().distributed,
+                    managed_biguint!(campaign_rewards)
+                );
+            },
+        )
+        .assert_ok();
+
+    let refund_amount = campaign_amount - campaign_rewards;
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let refund = sc.end_sponsor_campaign();
+                assert_eq!(refund, managed_biguint!(refund_amount));
+                assert!(sc.sponsor_campaign().is_empty());
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .check_esdt_balance(&sponsor, REWARD_TOKEN_ID, &rust_biguint!(refund_amount));
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &sponsor,
+            &setup.first_farm_wrapper,
+            REWARD_TOKEN_ID,
+            0,
+            &rust_biguint!(refund_amount),
+            |sc| {
+                sc.start_sponsor_campaign(1_000, 10);
+            },
+        )
+        .assert_ok();
+
+    // only the guild master may replace the active campaign
+    let guild_master_budget = 2_000_000;
+    setup.b_mock.set_esdt_balance(
+        &setup.first_owner_address,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(guild_master_budget),
+    );
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            REWARD_TOKEN_ID,
+            0,
+            &rust_biguint!(guild_master_budget),
+            |sc| {
+                sc.start_sponsor_campaign(500, 20);
+            },
+        )
+        .assert_user_error("Campaign already active");
+
+    // and only once its budget no longer covers the rest of the campaign
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            REWARD_TOKEN_ID,
+            0,
+            &rust_biguint!(guild_master_budget),
+            |sc| {
+                sc.start_sponsor_campaign(500, 20);
+            },
+        )
+        .assert_user_error("Campaign may not be replaced");
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount * 3),
+            |sc| {
+                let _ = sc.stake_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.first_owner_address,
+            &setup.first_farm_wrapper,
+            REWARD_TOKEN_ID,
+            0,
+            &rust_biguint!(guild_master_budget),
+            |sc| {
+                sc.start_sponsor_campaign(500, 20);
+
+                let campaign = sc.sponsor_campaign().get();
+                assert_eq!(
+                    campaign.sponsor,
+                    managed_address!(&setup.first_owner_address)
+                );
+                assert_eq!(campaign.end_epoch, current_epoch + 30);
+            },
+        )
+        .assert_ok();
+
+    // the previous sponsor is refunded
+    setup
+        .b_mock
+        .check_esdt_balance(&sponsor, REWARD_TOKEN_ID, &rust_biguint!(refund_amount));
+}
+
+#[test]
+fn protocol_fee_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+    let first_guild_addr = setup.first_farm_wrapper.address_ref().clone();
+    let treasury = setup.b_mock.create_user_account(&rust_biguint!(0));
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_protocol_fee(2_001);
+            },
+        )
+        .assert_user_error("Invalid value");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_protocol_fee(1_000);
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup.set_block_nonce(10);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.claim_rewards();
+
+                assert!(sc.accrued_protocol_fees().get() > 0);
+                assert_eq!(
+                    sc.accrued_protocol_fees().get(),
+                    sc.total_protocol_fees().get()
+                );
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut guilds = MultiValueEncoded::new();
+                guilds.push(managed_address!(&first_guild_addr));
+                let _ = sc.withdraw_protocol_fees(guilds);
+            },
+        )
+        .assert_user_error("No protocol fee treasury");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_protocol_fee_treasury(managed_address!(&treasury));
+            },
+        )
+        .assert_ok();
+
+    let mut withdrawn_fees = 0;
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut guilds = MultiValueEncoded::new();
+                guilds.push(managed_address!(&first_guild_addr));
+                let fees = sc.withdraw_protocol_fees(guilds);
+                assert!(fees > 0);
+                assert_eq!(sc.total_withdrawn_protocol_fees().get(), fees);
+                assert_eq!(
+                    sc.withdrawn_protocol_fees(&managed_address!(&first_guild_addr))
+                        .get(),
+                    fees
+                );
+
+                withdrawn_fees = fees.to_u64().unwrap();
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .check_esdt_balance(&treasury, REWARD_TOKEN_ID, &rust_biguint!(withdrawn_fees));
+
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            assert_eq!(sc.accrued_protocol_fees().get(), managed_biguint!(0));
+            assert!(sc.total_protocol_fees().get() > 0);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn suspend_guild_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let first_guild_addr = setup.first_farm_wrapper.address_ref().clone();
+    let suspension_reason = 3;
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.suspend_guild(managed_address!(&first_guild_addr), suspension_reason);
+            },
+        )
+        .assert_user_error("Endpoint can only be called by admins");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.suspend_guild(managed_address!(&first_guild_addr), suspension_reason);
+
+                let guild_id = sc.guild_ids().get_id(&managed_address!(&first_guild_addr));
+                assert!(!sc.active_guilds().contains(&guild_id));
+                assert_eq!(sc.suspension_reason(guild_id).get(), suspension_reason);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            assert!(!sc.produce_rewards_enabled().get());
+        })
+        .assert_ok();
+
+    // the guild master may not resume a suspended guild
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.resume_guild_endpoint(managed_address!(&first_guild_addr));
+            },
+        )
+        .assert_user_error("Guild suspended");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.suspend_guild(managed_address!(&first_guild_addr), suspension_reason);
+            },
+        )
+        .assert_user_error("Guild not active");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.unsuspend_guild(managed_address!(&first_guild_addr));
+
+                let guild_id = sc.guild_ids().get_id(&managed_address!(&first_guild_addr));
+                assert!(sc.active_guilds().contains(&guild_id));
+                assert!(sc.suspension_reason(guild_id).is_empty());
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            assert!(sc.produce_rewards_enabled().get());
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.unsuspend_guild(managed_address!(&first_guild_addr));
+            },
+        )
+        .assert_user_error("Guild not suspended");
+}
+
+#[test]
+fn undercollateralized_guild_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+    let first_guild_addr = setup.first_farm_wrapper.address_ref().clone();
+
+    let farm_in_amount = 100_000_000;
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_collateral_settings(1_000, 0);
+            },
+        )
+        .assert_user_error("Invalid value");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_collateral_settings(1_000, 2);
+            },
+        )
+        .assert_ok();
+
+    // the guild master's stake is below 10% of the guild's stake
+    setup.set_block_epoch(5);
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                assert!(sc.check_guild_master_collateral_endpoint());
+                assert_eq!(sc.undercollateralized_deadline_epoch().get(), 7);
+            },
+        )
+        .assert_ok();
+
+    setup.set_block_epoch(6);
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                assert!(sc.check_guild_master_collateral_endpoint());
+            },
+        )
+        .assert_ok();
+
+    // members' actions only keep track of the grace period
+    setup.set_block_epoch(7);
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.claim_rewards();
+
+                assert!(sc.produces_per_block_rewards());
+                assert_eq!(sc.undercollateralized_deadline_epoch().get(), 7);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                assert!(!sc.check_guild_master_collateral_endpoint());
+                assert!(sc.undercollateralized_deadline_epoch().is_empty());
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.factory_wrapper, |sc| {
+            let guild_id = sc.guild_ids().get_id(&managed_address!(&first_guild_addr));
+            assert!(!sc.active_guilds().contains(&guild_id));
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.resume_guild_endpoint(managed_address!(&first_guild_addr));
+            },
+        )
+        .assert_user_error("Guild master stake too low");
+
+    // a lower collateral ratio lets the guild master resume the guild
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_collateral_settings(0, 2);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.first_owner_address,
+            &setup.factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.resume_guild_endpoint(managed_address!(&first_guild_addr));
+
+                let guild_id = sc.guild_ids().get_id(&managed_address!(&first_guild_addr));
+                assert!(sc.active_guilds().contains(&guild_id));
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn global_staking_cap_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+
+    let mut total_staked = 0;
+    setup
+        .b_mock
+        .execute_query(&setup.config_wrapper, |sc| {
+            assert!(sc
+                .get_remaining_global_staking_capacity()
+                .into_option()
+                .is_none());
+
+            total_staked = sc.total_staking_token_staked().get().to_u64().unwrap();
+        })
+        .assert_ok();
+
+    let farm_in_amount = 1_000;
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_global_staking_cap(managed_biguint!(total_staked + farm_in_amount));
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.first_farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount + 1),
+            |sc| {
+                let _ = sc.stake_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_user_error("Global staking cap reached");
+
+    setup.stake_farm(farm_in_amount, &[], 2, 0, 0);
+
+    setup
+        .b_mock
+        .execute_query(&setup.config_wrapper, |sc| {
+            assert_eq!(
+                sc.get_remaining_global_staking_capacity().into_option(),
+                Some(managed_biguint!(0))
+            );
+        })
+        .assert_ok();
+
+    // removing the cap allows staking again
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_global_staking_cap(managed_biguint!(0));
+            },
+        )
+        .assert_ok();
+
+    setup.stake_farm(farm_in_amount, &[], 3, 0, 0);
+}
+
+#[test]
+fn average_staked_percent_test() {
+    DebugApi::dummy();
+
+    let mut setup = FarmStakingSetup::new(
+        guild_sc::contract_obj,
+        guild_sc_config::contract_obj,
+        guild_factory::contract_obj,
+    );
+    let factory_address = setup.factory_wrapper.address_ref().clone();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &factory_address,
+            &setup.config_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_total_staking_token_minted(managed_biguint!(USER_TOTAL_RIDE_TOKENS * 2));
+            },
+        )
+        .assert_ok();
+
+    // staking half of the minted tokens does not move the average right away
+    setup.stake_farm(USER_TOTAL_RIDE_TOKENS, &[], 2, 0, 0);
+
+    setup
+        .b_mock
+        .execute_query(&setup.config_wrapper, |sc| {
+            assert_eq!(sc.get_staked_percent(), 5_000);
+            assert_eq!(sc.get_average_staked_percent(), 0);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .set_block_timestamp(STAKED_PERCENT_AVERAGE_WINDOW_SECONDS / 2);
+
+    setup
+        .b_mock
+        .execute_query(&setup.config_wrapper, |sc| {
+            assert_eq!(sc.get_average_staked_percent(), 2_500);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.first_farm_wrapper, |sc| {
+            assert_eq!(sc.get_average_staked_percent(), 2_500);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .set_block_timestamp(STAKED_PERCENT_AVERAGE_WINDOW_SECONDS);
+
+    setup
+        .b_mock
+        .execute_query(&setup.config_wrapper, |sc| {
+            assert_eq!(sc.get_average_staked_percent(), 5_000);
+        })
+        .assert_ok();
+}
@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           26
+// Endpoints:                           56
 // Async Callback (empty):               1
-// Total number of exported functions:  29
+// Total number of exported functions:  59
 
 #![no_std]
 
@@ -23,6 +23,9 @@ multiversx_sc_wasm_adapter::endpoints! {
         deployConfigSc => deploy_config_sc
         upgradeConfigSc => upgrade_config_sc
         callConfigFunction => call_config_function
+        proposeConfigChange => propose_config_change
+        cancelConfigChange => cancel_config_change
+        executeConfigChange => execute_config_change
         getConfigAddress => config_sc_address
         setMaxActiveGuilds => set_max_active_guilds
         setGuildScSourceAddress => set_guild_sc_source_address
@@ -31,17 +34,44 @@ multiversx_sc_wasm_adapter::endpoints! {
         resumeGuild => resume_guild_endpoint
         getAllGuilds => get_all_guilds
         getAllActiveGuilds => get_all_active_guilds
+        getAllSuspendedGuilds => get_all_suspended_guilds
         getGuildId => get_guild_id
         getCurrentActiveGuilds => get_current_active_guilds
         getGuildScSourceAddress => guild_sc_source_address
+        getSuspensionReason => suspension_reason
         getRemainingRewards => remaining_rewards
         getMaxActiveGuilds => max_active_guilds
         requestRewards => request_rewards
         migrateToOtherGuild => migrate_to_other_guild
+        switchGuild => switch_guild
+        setGuildSwitchCooldownEpochs => set_guild_switch_cooldown_epochs
+        slashGuildMaster => slash_guild_master
+        setSlashingTreasury => set_slashing_treasury
+        suspendGuild => suspend_guild
+        unsuspendGuild => unsuspend_guild
+        withdrawProtocolFees => withdraw_protocol_fees
+        setProtocolFeeTreasury => set_protocol_fee_treasury
+        migrateClosedGuildMembers => migrate_closed_guild_members
+        claimUnmigratedTokens => claim_unmigrated_tokens
+        setDefaultMigrationGuild => set_default_migration_guild
+        setMigrationFallbackGuild => set_migration_fallback_guild
+        clearMigrationFallbackGuild => clear_migration_fallback_guild
         depositRewardsGuild => deposit_rewards_guild
         closeGuildNoRewardsRemaining => close_guild_no_rewards_remaining
+        deactivateGuild => deactivate_guild
+        reopenGuild => reopen_guild
+        transferGuildMaster => transfer_guild_master
         depositRewardsAdmins => deposit_rewards_admins
         getClosedGuilds => closed_guilds
+        getGuildSwitchCooldownEpochs => guild_switch_cooldown_epochs
+        getLastGuildSwitchEpoch => last_guild_switch_epoch
+        getDefaultMigrationGuild => default_migration_guild
+        getMigrationFallbackGuild => migration_fallback_guild
+        getUnmigratedTokens => unmigrated_tokens
+        getSlashingTreasury => slashing_treasury
+        getProtocolFeeTreasury => protocol_fee_treasury
+        getWithdrawnProtocolFeesForGuild => withdrawn_protocol_fees
+        getTotalWithdrawnProtocolFees => total_withdrawn_protocol_fees
         isAdmin => is_admin
         addAdmin => add_admin
         removeAdmin => remove_admin
@@ -0,0 +1,144 @@
+use common_structs::Percent;
+
+use crate::{roles::ConfigRole, tiers::MAX_PERCENT};
+
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct BoostedYieldsFactors<M: ManagedTypeApi> {
+    pub max_rewards_factor: BigUint<M>,
+    pub user_rewards_energy_const: BigUint<M>,
+    pub user_rewards_farm_const: BigUint<M>,
+    pub min_energy_amount: BigUint<M>,
+    pub min_farm_amount: BigUint<M>,
+}
+
+impl<M: ManagedTypeApi> Default for BoostedYieldsFactors<M> {
+    fn default() -> Self {
+        Self {
+            max_rewards_factor: BigUint::zero(),
+            user_rewards_energy_const: BigUint::zero(),
+            user_rewards_farm_const: BigUint::zero(),
+            min_energy_amount: BigUint::zero(),
+            min_farm_amount: BigUint::zero(),
+        }
+    }
+}
+
+#[multiversx_sc::module]
+pub trait BoostedYieldsConfigModule:
+    crate::roles::RolesModule
+    + crate::timelock::TimelockModule
+    + crate::config_events::ConfigEventsModule
+{
+    #[endpoint(setBoostedYieldsFactors)]
+    fn set_boosted_yields_factors(
+        &self,
+        max_rewards_factor: BigUint,
+        user_rewards_energy_const: BigUint,
+        user_rewards_farm_const: BigUint,
+        min_energy_amount: BigUint,
+        min_farm_amount: BigUint,
+    ) {
+        self.require_caller_has_role(ConfigRole::EmissionsManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(&max_rewards_factor);
+        args.push_arg(&user_rewards_energy_const);
+        args.push_arg(&user_rewards_farm_const);
+        args.push_arg(&min_energy_amount);
+        args.push_arg(&min_farm_amount);
+        self.require_change_unlocked(b"setBoostedYieldsFactors", args);
+
+        require!(
+            max_rewards_factor > 0
+                && (user_rewards_energy_const > 0 || user_rewards_farm_const > 0),
+            "Invalid boosted yields factors"
+        );
+
+        let old_factors = self.get_boosted_yields_factors();
+        let new_factors = BoostedYieldsFactors {
+            max_rewards_factor,
+            user_rewards_energy_const,
+            user_rewards_farm_const,
+            min_energy_amount,
+            min_farm_amount,
+        };
+        self.boosted_yields_factors().set(&new_factors);
+
+        self.emit_boosted_yields_factors_change(old_factors, new_factors);
+    }
+
+    /// Percentage of the user rewards that is distributed weekly, based on energy
+    #[endpoint(setBoostedYieldsRewardsPercentage)]
+    fn set_boosted_yields_rewards_percentage(&self, percentage: Percent) {
+        self.require_caller_has_role(ConfigRole::EmissionsManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(percentage);
+        self.require_change_unlocked(b"setBoostedYieldsRewardsPercentage", args);
+
+        require!(percentage <= MAX_PERCENT, "Invalid percentage");
+        require!(
+            percentage == 0 || !self.boosted_yields_factors().is_empty(),
+            "Boosted yields factors not set"
+        );
+
+        let old_percentage = self.boosted_yields_rewards_percentage().get();
+        self.boosted_yields_rewards_percentage().set(percentage);
+
+        self.emit_boosted_yields_rewards_percentage_change(old_percentage, percentage);
+    }
+
+    #[endpoint(setEnergyFactoryAddress)]
+    fn set_energy_factory_address(&self, sc_address: ManagedAddress) {
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(&sc_address);
+        self.require_change_unlocked(b"setEnergyFactoryAddress", args);
+
+        require!(
+            self.blockchain().is_smart_contract(&sc_address),
+            "Invalid energy factory address"
+        );
+
+        let old_sc_address = self.get_energy_factory_address();
+        self.energy_factory_address().set(&sc_address);
+
+        self.emit_energy_factory_address_change(&old_sc_address, &sc_address);
+    }
+
+    /// Returns zero factors if not set, meaning boosted yields are disabled
+    #[view(getBoostedYieldsFactors)]
+    fn get_boosted_yields_factors(&self) -> BoostedYieldsFactors<Self::Api> {
+        let mapper = self.boosted_yields_factors();
+        if !mapper.is_empty() {
+            mapper.get()
+        } else {
+            BoostedYieldsFactors::default()
+        }
+    }
+
+    /// Returns the zero address if not set
+    #[view(getEnergyFactoryAddress)]
+    fn get_energy_factory_address(&self) -> ManagedAddress {
+        let mapper = self.energy_factory_address();
+        if !mapper.is_empty() {
+            mapper.get()
+        } else {
+            ManagedAddress::zero()
+        }
+    }
+
+    #[storage_mapper("boostedYieldsFactors")]
+    fn boosted_yields_factors(&self) -> SingleValueMapper<BoostedYieldsFactors<Self::Api>>;
+
+    #[view(getBoostedYieldsRewardsPercentage)]
+    #[storage_mapper("boostedYieldsRewardsPercentage")]
+    fn boosted_yields_rewards_percentage(&self) -> SingleValueMapper<Percent>;
+
+    #[storage_mapper("energyFactoryAddress")]
+    fn energy_factory_address(&self) -> SingleValueMapper<ManagedAddress>;
+}
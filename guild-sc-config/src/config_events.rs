@@ -0,0 +1,631 @@
+use common_structs::{Epoch, Percent};
+
+use crate::{
+    boosted_yields::BoostedYieldsFactors,
+    global_config::ClosedGuildExitMode,
+    tier_types::{GuildMasterRewardTier, TierCurveMode, UserRewardTier},
+};
+
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub type ChangelogEntryId = u64;
+
+pub const MAX_CHANGELOG_ENTRIES: ChangelogEntryId = 100;
+pub const MAX_CHANGELOG_PAGE_SIZE: usize = 20;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct ChangelogEntry<M: ManagedTypeApi> {
+    pub setting: ManagedBuffer<M>,
+    pub old_value: ManagedBuffer<M>,
+    pub new_value: ManagedBuffer<M>,
+    pub caller: ManagedAddress<M>,
+    pub block: u64,
+    pub epoch: Epoch,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct GuildMasterTiersChange<M: ManagedTypeApi> {
+    pub old_tiers: ManagedVec<M, GuildMasterRewardTier<M>>,
+    pub new_tiers: ManagedVec<M, GuildMasterRewardTier<M>>,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct BoostedYieldsFactorsChange<M: ManagedTypeApi> {
+    pub old_factors: BoostedYieldsFactors<M>,
+    pub new_factors: BoostedYieldsFactors<M>,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct UserTiersChange<M: ManagedTypeApi> {
+    pub old_tiers: ManagedVec<M, UserRewardTier>,
+    pub new_tiers: ManagedVec<M, UserRewardTier>,
+}
+
+#[multiversx_sc::module]
+pub trait ConfigEventsModule {
+    fn emit_min_stake_user_change(&self, old_value: &BigUint, new_value: &BigUint) {
+        let caller = self.blockchain().get_caller();
+        self.min_stake_user_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setMinStakeUser", &caller, old_value, new_value);
+    }
+
+    fn emit_min_stake_guild_master_change(&self, old_value: &BigUint, new_value: &BigUint) {
+        let caller = self.blockchain().get_caller();
+        self.min_stake_guild_master_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setMinStakeGuildMaster", &caller, old_value, new_value);
+    }
+
+    fn emit_min_unbond_epochs_user_change(&self, old_value: Epoch, new_value: Epoch) {
+        let caller = self.blockchain().get_caller();
+        self.min_unbond_epochs_user_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setMinUnbondEpochsUser", &caller, &old_value, &new_value);
+    }
+
+    fn emit_min_unbond_epochs_guild_master_change(&self, old_value: Epoch, new_value: Epoch) {
+        let caller = self.blockchain().get_caller();
+        self.min_unbond_epochs_guild_master_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(
+            b"setMinUnbondEpochsGuildMaster",
+            &caller,
+            &old_value,
+            &new_value,
+        );
+    }
+
+    fn emit_unbond_epochs_bounds_change(
+        &self,
+        old_bounds: (Epoch, Epoch),
+        new_bounds: (Epoch, Epoch),
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.unbond_epochs_bounds_changed_event(
+            &caller,
+            old_bounds.0,
+            old_bounds.1,
+            new_bounds.0,
+            new_bounds.1,
+        );
+        self.add_changelog_entry(b"setUnbondEpochsBounds", &caller, &old_bounds, &new_bounds);
+    }
+
+    fn emit_total_staking_token_minted_change(&self, old_value: &BigUint, new_value: &BigUint) {
+        let caller = self.blockchain().get_caller();
+        self.total_staking_token_minted_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setTotalStakingTokenMinted", &caller, old_value, new_value);
+    }
+
+    fn emit_global_staking_cap_change(&self, old_value: &BigUint, new_value: &BigUint) {
+        let caller = self.blockchain().get_caller();
+        self.global_staking_cap_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setGlobalStakingCap", &caller, old_value, new_value);
+    }
+
+    fn emit_seconds_per_block_change(&self, old_value: u64, new_value: u64) {
+        let caller = self.blockchain().get_caller();
+        self.seconds_per_block_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setSecondsPerBlock", &caller, &old_value, &new_value);
+    }
+
+    fn emit_per_block_reward_amount_change(&self, old_value: &BigUint, new_value: &BigUint) {
+        let caller = self.blockchain().get_caller();
+        self.per_block_reward_amount_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setPerBlockRewardAmount", &caller, old_value, new_value);
+    }
+
+    fn emit_global_pause_status_change(&self, old_value: bool, new_value: bool) {
+        let caller = self.blockchain().get_caller();
+        self.global_pause_status_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"globalPauseStatus", &caller, &old_value, &new_value);
+    }
+
+    fn emit_max_staked_tokens_change(&self, old_value: &BigUint, new_value: &BigUint) {
+        let caller = self.blockchain().get_caller();
+        self.max_staked_tokens_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setMaxStakedTokens", &caller, old_value, new_value);
+    }
+
+    fn emit_guild_master_tiers_change(
+        &self,
+        old_tiers: ManagedVec<GuildMasterRewardTier<Self::Api>>,
+        new_tiers: ManagedVec<GuildMasterRewardTier<Self::Api>>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.add_changelog_entry(b"guildMasterTiers", &caller, &old_tiers, &new_tiers);
+
+        let change = GuildMasterTiersChange {
+            old_tiers,
+            new_tiers,
+        };
+        self.guild_master_tiers_changed_event(&caller, &change);
+    }
+
+    fn emit_guild_master_tier_apr_change(
+        &self,
+        max_stake: &BigUint,
+        old_value: Percent,
+        new_value: Percent,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.guild_master_tier_apr_changed_event(&caller, max_stake, old_value, new_value);
+
+        // the tiers are recorded, so the changelog shows which tier changed
+        let old_tier = GuildMasterRewardTier {
+            max_stake: max_stake.clone(),
+            apr: old_value,
+        };
+        let new_tier = GuildMasterRewardTier {
+            max_stake: max_stake.clone(),
+            apr: new_value,
+        };
+        self.add_changelog_entry(b"setGuildMasterTierApr", &caller, &old_tier, &new_tier);
+    }
+
+    fn emit_user_tiers_change(
+        &self,
+        old_tiers: ManagedVec<UserRewardTier>,
+        new_tiers: ManagedVec<UserRewardTier>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.add_changelog_entry(b"userTiers", &caller, &old_tiers, &new_tiers);
+
+        let change = UserTiersChange {
+            old_tiers,
+            new_tiers,
+        };
+        self.user_tiers_changed_event(&caller, &change);
+    }
+
+    fn emit_user_tier_apr_change(
+        &self,
+        max_percentage_staked: Percent,
+        old_value: Percent,
+        new_value: Percent,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.user_tier_apr_changed_event(&caller, max_percentage_staked, old_value, new_value);
+
+        let old_tier = UserRewardTier {
+            max_percentage_staked,
+            apr: old_value,
+        };
+        let new_tier = UserRewardTier {
+            max_percentage_staked,
+            apr: new_value,
+        };
+        self.add_changelog_entry(b"setUserTierApr", &caller, &old_tier, &new_tier);
+    }
+
+    fn emit_guild_master_tiers_curve_mode_change(
+        &self,
+        old_value: TierCurveMode,
+        new_value: TierCurveMode,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.guild_master_tiers_curve_mode_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(
+            b"setGuildMasterTiersCurveMode",
+            &caller,
+            &old_value,
+            &new_value,
+        );
+    }
+
+    fn emit_user_tiers_curve_mode_change(
+        &self,
+        old_value: TierCurveMode,
+        new_value: TierCurveMode,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.user_tiers_curve_mode_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setUserTiersCurveMode", &caller, &old_value, &new_value);
+    }
+
+    fn emit_timelock_epochs_change(&self, old_value: Epoch, new_value: Epoch) {
+        let caller = self.blockchain().get_caller();
+        self.timelock_epochs_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setTimelockEpochs", &caller, &old_value, &new_value);
+    }
+
+    fn emit_boosted_yields_factors_change(
+        &self,
+        old_factors: BoostedYieldsFactors<Self::Api>,
+        new_factors: BoostedYieldsFactors<Self::Api>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.add_changelog_entry(
+            b"setBoostedYieldsFactors",
+            &caller,
+            &old_factors,
+            &new_factors,
+        );
+
+        let change = BoostedYieldsFactorsChange {
+            old_factors,
+            new_factors,
+        };
+        self.boosted_yields_factors_changed_event(&caller, &change);
+    }
+
+    fn emit_boosted_yields_rewards_percentage_change(
+        &self,
+        old_value: Percent,
+        new_value: Percent,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.boosted_yields_rewards_percentage_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(
+            b"setBoostedYieldsRewardsPercentage",
+            &caller,
+            &old_value,
+            &new_value,
+        );
+    }
+
+    fn emit_energy_factory_address_change(
+        &self,
+        old_value: &ManagedAddress,
+        new_value: &ManagedAddress,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.energy_factory_address_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setEnergyFactoryAddress", &caller, old_value, new_value);
+    }
+
+    /// A zero address means no pair is set
+    fn emit_swap_pair_change(
+        &self,
+        token_id: &TokenIdentifier,
+        old_value: &ManagedAddress,
+        new_value: &ManagedAddress,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.swap_pair_changed_event(&caller, token_id, old_value, new_value);
+        self.add_changelog_entry(b"setSwapPair", &caller, old_value, new_value);
+    }
+
+    fn emit_closed_guild_exit_mode_change(
+        &self,
+        old_value: ClosedGuildExitMode,
+        new_value: ClosedGuildExitMode,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.closed_guild_exit_mode_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setClosedGuildExitMode", &caller, &old_value, &new_value);
+    }
+
+    fn emit_governance_settings_change(
+        &self,
+        old_settings: (Epoch, Percent),
+        new_settings: (Epoch, Percent),
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.governance_settings_changed_event(
+            &caller,
+            old_settings.0,
+            old_settings.1,
+            new_settings.0,
+            new_settings.1,
+        );
+        self.add_changelog_entry(
+            b"setGovernanceSettings",
+            &caller,
+            &old_settings,
+            &new_settings,
+        );
+    }
+
+    fn emit_protocol_fee_change(&self, old_value: Percent, new_value: Percent) {
+        let caller = self.blockchain().get_caller();
+        self.protocol_fee_changed_event(&caller, old_value, new_value);
+        self.add_changelog_entry(b"setProtocolFee", &caller, &old_value, &new_value);
+    }
+
+    fn emit_collateral_settings_change(
+        &self,
+        old_settings: (Percent, Epoch),
+        new_settings: (Percent, Epoch),
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.collateral_settings_changed_event(
+            &caller,
+            old_settings.0,
+            old_settings.1,
+            new_settings.0,
+            new_settings.1,
+        );
+        self.add_changelog_entry(
+            b"setCollateralSettings",
+            &caller,
+            &old_settings,
+            &new_settings,
+        );
+    }
+
+    /// Only the last MAX_CHANGELOG_ENTRIES entries are kept
+    fn add_changelog_entry<T: TopEncode>(
+        &self,
+        setting: &[u8],
+        caller: &ManagedAddress,
+        old_value: &T,
+        new_value: &T,
+    ) {
+        let mut encoded_old_value = ManagedBuffer::new();
+        let _ = old_value.top_encode(&mut encoded_old_value);
+
+        let mut encoded_new_value = ManagedBuffer::new();
+        let _ = new_value.top_encode(&mut encoded_new_value);
+
+        let entry = ChangelogEntry {
+            setting: ManagedBuffer::new_from_bytes(setting),
+            old_value: encoded_old_value,
+            new_value: encoded_new_value,
+            caller: caller.clone(),
+            block: self.blockchain().get_block_nonce(),
+            epoch: self.blockchain().get_block_epoch(),
+        };
+
+        let entry_id = self.last_changelog_entry_id().update(|id| {
+            *id += 1;
+            *id
+        });
+        self.changelog_entry(entry_id).set(entry);
+
+        if entry_id > MAX_CHANGELOG_ENTRIES {
+            self.changelog_entry(entry_id - MAX_CHANGELOG_ENTRIES)
+                .clear();
+        }
+    }
+
+    /// Returns at most MAX_CHANGELOG_PAGE_SIZE entries, starting with from_id.
+    /// Entries older than the last MAX_CHANGELOG_ENTRIES are skipped, as they are no longer stored.
+    #[view(getConfigChangelog)]
+    fn get_config_changelog(
+        &self,
+        from_id: ChangelogEntryId,
+        page_size: usize,
+    ) -> MultiValueEncoded<MultiValue2<ChangelogEntryId, ChangelogEntry<Self::Api>>> {
+        let last_id = self.last_changelog_entry_id().get();
+        let oldest_stored_id = if last_id > MAX_CHANGELOG_ENTRIES {
+            last_id - MAX_CHANGELOG_ENTRIES + 1
+        } else {
+            1
+        };
+        let page_size = core::cmp::min(page_size, MAX_CHANGELOG_PAGE_SIZE);
+
+        let mut result = MultiValueEncoded::new();
+        let mut entry_id = core::cmp::max(from_id, oldest_stored_id);
+        while entry_id <= last_id && result.len() < page_size {
+            let entry = self.changelog_entry(entry_id).get();
+            result.push((entry_id, entry).into());
+
+            entry_id += 1;
+        }
+
+        result
+    }
+
+    #[event("minStakeUserChanged")]
+    fn min_stake_user_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: &BigUint,
+        #[indexed] new_value: &BigUint,
+    );
+
+    #[event("minStakeGuildMasterChanged")]
+    fn min_stake_guild_master_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: &BigUint,
+        #[indexed] new_value: &BigUint,
+    );
+
+    #[event("minUnbondEpochsUserChanged")]
+    fn min_unbond_epochs_user_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: Epoch,
+        #[indexed] new_value: Epoch,
+    );
+
+    #[event("minUnbondEpochsGuildMasterChanged")]
+    fn min_unbond_epochs_guild_master_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: Epoch,
+        #[indexed] new_value: Epoch,
+    );
+
+    #[event("unbondEpochsBoundsChanged")]
+    fn unbond_epochs_bounds_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_lower_bound: Epoch,
+        #[indexed] old_upper_bound: Epoch,
+        #[indexed] new_lower_bound: Epoch,
+        #[indexed] new_upper_bound: Epoch,
+    );
+
+    #[event("totalStakingTokenMintedChanged")]
+    fn total_staking_token_minted_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: &BigUint,
+        #[indexed] new_value: &BigUint,
+    );
+
+    #[event("globalStakingCapChanged")]
+    fn global_staking_cap_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: &BigUint,
+        #[indexed] new_value: &BigUint,
+    );
+
+    #[event("secondsPerBlockChanged")]
+    fn seconds_per_block_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: u64,
+        #[indexed] new_value: u64,
+    );
+
+    #[event("perBlockRewardAmountChanged")]
+    fn per_block_reward_amount_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: &BigUint,
+        #[indexed] new_value: &BigUint,
+    );
+
+    #[event("globalPauseStatusChanged")]
+    fn global_pause_status_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: bool,
+        #[indexed] new_value: bool,
+    );
+
+    #[event("maxStakedTokensChanged")]
+    fn max_staked_tokens_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: &BigUint,
+        #[indexed] new_value: &BigUint,
+    );
+
+    #[event("guildMasterTiersChanged")]
+    fn guild_master_tiers_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        change: &GuildMasterTiersChange<Self::Api>,
+    );
+
+    #[event("guildMasterTierAprChanged")]
+    fn guild_master_tier_apr_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] max_stake: &BigUint,
+        #[indexed] old_value: Percent,
+        #[indexed] new_value: Percent,
+    );
+
+    #[event("userTiersChanged")]
+    fn user_tiers_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        change: &UserTiersChange<Self::Api>,
+    );
+
+    #[event("userTierAprChanged")]
+    fn user_tier_apr_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] max_percentage_staked: Percent,
+        #[indexed] old_value: Percent,
+        #[indexed] new_value: Percent,
+    );
+
+    #[event("guildMasterTiersCurveModeChanged")]
+    fn guild_master_tiers_curve_mode_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: TierCurveMode,
+        #[indexed] new_value: TierCurveMode,
+    );
+
+    #[event("userTiersCurveModeChanged")]
+    fn user_tiers_curve_mode_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: TierCurveMode,
+        #[indexed] new_value: TierCurveMode,
+    );
+
+    #[event("timelockEpochsChanged")]
+    fn timelock_epochs_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: Epoch,
+        #[indexed] new_value: Epoch,
+    );
+
+    #[event("boostedYieldsFactorsChanged")]
+    fn boosted_yields_factors_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        change: &BoostedYieldsFactorsChange<Self::Api>,
+    );
+
+    #[event("boostedYieldsRewardsPercentageChanged")]
+    fn boosted_yields_rewards_percentage_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: Percent,
+        #[indexed] new_value: Percent,
+    );
+
+    #[event("energyFactoryAddressChanged")]
+    fn energy_factory_address_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: &ManagedAddress,
+        #[indexed] new_value: &ManagedAddress,
+    );
+
+    #[event("swapPairChanged")]
+    fn swap_pair_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] token_id: &TokenIdentifier,
+        #[indexed] old_value: &ManagedAddress,
+        #[indexed] new_value: &ManagedAddress,
+    );
+
+    #[event("closedGuildExitModeChanged")]
+    fn closed_guild_exit_mode_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: ClosedGuildExitMode,
+        #[indexed] new_value: ClosedGuildExitMode,
+    );
+
+    #[event("governanceSettingsChanged")]
+    fn governance_settings_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_voting_period_epochs: Epoch,
+        #[indexed] old_quorum: Percent,
+        #[indexed] new_voting_period_epochs: Epoch,
+        #[indexed] new_quorum: Percent,
+    );
+
+    #[event("collateralSettingsChanged")]
+    fn collateral_settings_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_collateral_ratio: Percent,
+        #[indexed] old_grace_epochs: Epoch,
+        #[indexed] new_collateral_ratio: Percent,
+        #[indexed] new_grace_epochs: Epoch,
+    );
+
+    #[event("protocolFeeChanged")]
+    fn protocol_fee_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_value: Percent,
+        #[indexed] new_value: Percent,
+    );
+
+    #[view(getLastChangelogEntryId)]
+    #[storage_mapper("lastChangelogEntryId")]
+    fn last_changelog_entry_id(&self) -> SingleValueMapper<ChangelogEntryId>;
+
+    #[storage_mapper("changelogEntry")]
+    fn changelog_entry(
+        &self,
+        entry_id: ChangelogEntryId,
+    ) -> SingleValueMapper<ChangelogEntry<Self::Api>>;
+}
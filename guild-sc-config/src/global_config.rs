@@ -1,42 +1,154 @@
-use common_structs::Epoch;
+use common_structs::{Epoch, Percent};
+
+use crate::{roles::ConfigRole, tiers::MAX_PERCENT};
 
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
 
 pub static INVALID_MIN_UNBOND_EPOCHS_ERR_MSG: &[u8] = b"Invalid min unbond epochs";
 static INVALID_VALUE_ERR_MSG: &[u8] = b"Invalid value";
+pub static NO_SWAP_PAIR_ERR_MSG: &[u8] = b"No swap pair for token";
+pub static GLOBAL_STAKING_CAP_REACHED_ERR_MSG: &[u8] = b"Global staking cap reached";
 
 pub const MAX_MIN_UNBOND_EPOCHS: Epoch = 30;
+pub const MAX_UNBOND_EPOCHS_LIMIT: Epoch = 365;
+
+pub const DEFAULT_PROPOSAL_VOTING_PERIOD_EPOCHS: Epoch = 7;
+pub const DEFAULT_PROPOSAL_QUORUM: Percent = 2_000;
+
+pub const MAX_PROTOCOL_FEE: Percent = 2_000;
+
+pub const STAKED_PERCENT_AVERAGE_WINDOW_SECONDS: u64 = 86_400;
 
 pub type GlobalPauseStatus = bool;
 pub const PAUSED: bool = true;
 pub const UNPAUSED: bool = false;
 
+/// DirectPayout: members of closing guilds receive their farming tokens right away when unstaking.
+/// UnlockedUnbondToken: they receive an unbond token which may be redeemed right away.
+/// Unbond: they wait for the usual unbond epochs.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum ClosedGuildExitMode {
+    DirectPayout,
+    UnlockedUnbondToken,
+    Unbond,
+}
+
+/// Time-weighted average of the staked percentage, so that briefly staking large amounts
+/// only moves the average by the fraction of the window the stake was kept for
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct StakedPercentAverage {
+    pub average: Percent,
+    pub last_staked_percent: Percent,
+    pub last_update_timestamp: u64,
+}
+
+impl StakedPercentAverage {
+    pub fn new(staked_percent: Percent, timestamp: u64) -> Self {
+        StakedPercentAverage {
+            average: staked_percent,
+            last_staked_percent: staked_percent,
+            last_update_timestamp: timestamp,
+        }
+    }
+
+    pub fn current_average(&self, timestamp: u64) -> Percent {
+        let elapsed = core::cmp::min(
+            timestamp.saturating_sub(self.last_update_timestamp),
+            STAKED_PERCENT_AVERAGE_WINDOW_SECONDS,
+        );
+        let remaining = STAKED_PERCENT_AVERAGE_WINDOW_SECONDS - elapsed;
+
+        (self.average * remaining + self.last_staked_percent * elapsed)
+            / STAKED_PERCENT_AVERAGE_WINDOW_SECONDS
+    }
+
+    pub fn update(&mut self, staked_percent: Percent, timestamp: u64) {
+        self.average = self.current_average(timestamp);
+        self.last_staked_percent = staked_percent;
+        self.last_update_timestamp = timestamp;
+    }
+}
+
 #[multiversx_sc::module]
-pub trait GlobalConfigModule {
-    #[only_owner]
+pub trait GlobalConfigModule:
+    crate::roles::RolesModule
+    + crate::timelock::TimelockModule
+    + crate::config_events::ConfigEventsModule
+{
     #[endpoint(setMinStakeUser)]
     fn set_min_stake_user(&self, min_stake: BigUint) {
-        self.min_stake_user().set(min_stake);
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(&min_stake);
+        self.require_change_unlocked(b"setMinStakeUser", args);
+
+        let old_min_stake = self.min_stake_user().get();
+        self.min_stake_user().set(&min_stake);
+
+        self.emit_min_stake_user_change(&old_min_stake, &min_stake);
     }
 
-    #[only_owner]
     #[endpoint(setMinStakeGuildMaster)]
     fn set_min_stake_guild_master(&self, min_stake: BigUint) {
-        self.min_stake_guild_master().set(min_stake);
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(&min_stake);
+        self.require_change_unlocked(b"setMinStakeGuildMaster", args);
+
+        let old_min_stake = self.min_stake_guild_master().get();
+        self.min_stake_guild_master().set(&min_stake);
+
+        self.emit_min_stake_guild_master_change(&old_min_stake, &min_stake);
     }
 
-    #[only_owner]
     #[endpoint(setTotalStakingTokenMinted)]
     fn set_total_staking_token_minted(&self, total_minted: BigUint) {
-        self.total_staking_token_minted().set(total_minted);
+        self.require_caller_has_role(ConfigRole::EmissionsManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(&total_minted);
+        self.require_change_unlocked(b"setTotalStakingTokenMinted", args);
+
+        let old_total_minted = self.total_staking_token_minted().get();
+        self.total_staking_token_minted().set(&total_minted);
+        self.update_staked_percent_average();
+
+        self.emit_total_staking_token_minted_change(&old_total_minted, &total_minted);
+    }
+
+    /// Caps the tokens staked across all guilds. Zero removes the cap.
+    /// A cap below the current total only prevents new stakes.
+    #[endpoint(setGlobalStakingCap)]
+    fn set_global_staking_cap(&self, cap: BigUint) {
+        self.require_caller_has_role(ConfigRole::EmissionsManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(&cap);
+        self.require_change_unlocked(b"setGlobalStakingCap", args);
+
+        let old_cap = self.global_staking_cap().get();
+        self.global_staking_cap().set(&cap);
+
+        self.emit_global_staking_cap_change(&old_cap, &cap);
     }
 
     #[endpoint(increaseStakedTokens)]
     fn increase_staked_tokens(&self, amount: BigUint) {
         self.require_guild_sc_caller();
 
-        self.total_staking_token_staked()
-            .update(|total| *total += amount);
+        let cap = self.global_staking_cap().get();
+        self.total_staking_token_staked().update(|total| {
+            *total += amount;
+
+            require!(
+                cap == 0 || *total <= cap,
+                GLOBAL_STAKING_CAP_REACHED_ERR_MSG
+            );
+        });
+        self.update_staked_percent_average();
     }
 
     #[endpoint(decreaseStakedTokens)]
@@ -45,56 +157,358 @@ pub trait GlobalConfigModule {
 
         self.total_staking_token_staked()
             .update(|total| *total -= amount);
+        self.update_staked_percent_average();
+    }
+
+    /// Returns nothing if there is no global staking cap
+    #[view(getRemainingGlobalStakingCapacity)]
+    fn get_remaining_global_staking_capacity(&self) -> OptionalValue<BigUint> {
+        let cap = self.global_staking_cap().get();
+        if cap == 0 {
+            return OptionalValue::None;
+        }
+
+        let total_staked = self.total_staking_token_staked().get();
+        if total_staked >= cap {
+            return OptionalValue::Some(BigUint::zero());
+        }
+
+        OptionalValue::Some(cap - total_staked)
+    }
+
+    /// Must be called after each change of the staked or minted totals
+    fn update_staked_percent_average(&self) {
+        let staked_percent = self.get_staked_percent();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let average_mapper = self.staked_percent_average();
+        if average_mapper.is_empty() {
+            average_mapper.set(StakedPercentAverage::new(staked_percent, current_timestamp));
+
+            return;
+        }
+
+        average_mapper.update(|average| average.update(staked_percent, current_timestamp));
+    }
+
+    #[view(getStakedPercent)]
+    fn get_staked_percent(&self) -> Percent {
+        let total_minted = self.total_staking_token_minted().get();
+        if total_minted == 0 {
+            return 0;
+        }
+
+        let total_staked = self.total_staking_token_staked().get();
+        let opt_result = (total_staked * MAX_PERCENT / total_minted).to_u64();
+        require!(opt_result.is_some(), "Math failure");
+
+        unsafe { opt_result.unwrap_unchecked() }
+    }
+
+    /// Used by the guilds to find the user tier
+    #[view(getAverageStakedPercent)]
+    fn get_average_staked_percent(&self) -> Percent {
+        let average_mapper = self.staked_percent_average();
+        if average_mapper.is_empty() {
+            return self.get_staked_percent();
+        }
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        average_mapper.get().current_average(current_timestamp)
     }
 
-    #[only_owner]
     #[endpoint(setSecondsPerBlock)]
     fn set_seconds_per_block(&self, new_seconds_per_block: u64) {
+        self.require_caller_has_role(ConfigRole::EmissionsManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(new_seconds_per_block);
+        self.require_change_unlocked(b"setSecondsPerBlock", args);
+
         require!(new_seconds_per_block > 0, INVALID_VALUE_ERR_MSG);
 
+        let old_seconds_per_block = self.seconds_per_block().get();
         self.seconds_per_block().set(new_seconds_per_block);
+
+        self.emit_seconds_per_block_change(old_seconds_per_block, new_seconds_per_block);
     }
 
-    #[only_owner]
     #[endpoint(setPerBlockRewardAmount)]
     fn set_per_block_reward_amount(&self, new_per_block_reward_amount: BigUint) {
+        self.require_caller_has_role(ConfigRole::EmissionsManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(&new_per_block_reward_amount);
+        self.require_change_unlocked(b"setPerBlockRewardAmount", args);
+
         require!(new_per_block_reward_amount > 0, INVALID_VALUE_ERR_MSG);
 
+        let old_per_block_reward_amount = self.per_block_reward_amount().get();
         self.per_block_reward_amount()
-            .set(new_per_block_reward_amount);
+            .set(&new_per_block_reward_amount);
+
+        self.emit_per_block_reward_amount_change(
+            &old_per_block_reward_amount,
+            &new_per_block_reward_amount,
+        );
     }
 
-    #[only_owner]
     #[endpoint(pauseAllGuilds)]
     fn pause_all_guilds(&self) {
+        self.require_caller_has_role(ConfigRole::Pauser);
+
+        let old_status = self.global_pause_status().get();
         self.global_pause_status().set(PAUSED);
+
+        self.emit_global_pause_status_change(old_status, PAUSED);
     }
 
-    #[only_owner]
     #[endpoint(unpauseAllGuilds)]
     fn unpause_all_guilds(&self) {
+        self.require_caller_has_role(ConfigRole::Pauser);
+
+        let old_status = self.global_pause_status().get();
         self.global_pause_status().set(UNPAUSED);
+
+        self.emit_global_pause_status_change(old_status, UNPAUSED);
     }
 
+    /// Only affects new unbonds. Existing unbond tokens keep their unlock epoch.
+    #[endpoint(setMinUnbondEpochsUser)]
     fn set_min_unbond_epochs_user(&self, min_unbond_epochs: Epoch) {
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(min_unbond_epochs);
+        self.require_change_unlocked(b"setMinUnbondEpochsUser", args);
+
         self.require_valid_unbond_epochs(min_unbond_epochs);
 
+        let old_min_unbond_epochs = self.min_unbond_epochs_user().get();
         self.min_unbond_epochs_user().set(min_unbond_epochs);
+
+        self.emit_min_unbond_epochs_user_change(old_min_unbond_epochs, min_unbond_epochs);
     }
 
+    /// Only affects new unbonds. Existing unbond tokens keep their unlock epoch.
+    #[endpoint(setMinUnbondEpochsGuildMaster)]
     fn set_min_unbond_epochs_guild_master(&self, min_unbond_epochs: Epoch) {
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(min_unbond_epochs);
+        self.require_change_unlocked(b"setMinUnbondEpochsGuildMaster", args);
+
         self.require_valid_unbond_epochs(min_unbond_epochs);
 
+        let old_min_unbond_epochs = self.min_unbond_epochs_guild_master().get();
         self.min_unbond_epochs_guild_master().set(min_unbond_epochs);
+
+        self.emit_min_unbond_epochs_guild_master_change(old_min_unbond_epochs, min_unbond_epochs);
+    }
+
+    /// The min unbond epochs of users and guild master, as well as the per-guild overrides,
+    /// must be within these bounds
+    #[endpoint(setUnbondEpochsBounds)]
+    fn set_unbond_epochs_bounds(&self, lower_bound: Epoch, upper_bound: Epoch) {
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(lower_bound);
+        args.push_arg(upper_bound);
+        self.require_change_unlocked(b"setUnbondEpochsBounds", args);
+
+        require!(
+            lower_bound <= upper_bound && upper_bound <= MAX_UNBOND_EPOCHS_LIMIT,
+            "Invalid bounds"
+        );
+
+        let old_bounds = self.get_unbond_epochs_bounds().into_tuple();
+        self.unbond_epochs_lower_bound().set(lower_bound);
+        self.unbond_epochs_upper_bound().set(upper_bound);
+
+        self.require_valid_unbond_epochs(self.min_unbond_epochs_user().get());
+        self.require_valid_unbond_epochs(self.min_unbond_epochs_guild_master().get());
+
+        self.emit_unbond_epochs_bounds_change(old_bounds, (lower_bound, upper_bound));
     }
 
     fn require_valid_unbond_epochs(&self, unbond_epochs: Epoch) {
+        let (lower_bound, upper_bound) = self.get_unbond_epochs_bounds().into_tuple();
         require!(
-            unbond_epochs <= MAX_MIN_UNBOND_EPOCHS,
+            unbond_epochs >= lower_bound && unbond_epochs <= upper_bound,
             INVALID_MIN_UNBOND_EPOCHS_ERR_MSG
         );
     }
 
+    /// Upper bound defaults to MAX_MIN_UNBOND_EPOCHS
+    #[view(getUnbondEpochsBounds)]
+    fn get_unbond_epochs_bounds(&self) -> MultiValue2<Epoch, Epoch> {
+        let lower_bound = self.unbond_epochs_lower_bound().get();
+        let upper_bound_mapper = self.unbond_epochs_upper_bound();
+        let upper_bound = if !upper_bound_mapper.is_empty() {
+            upper_bound_mapper.get()
+        } else {
+            MAX_MIN_UNBOND_EPOCHS
+        };
+
+        (lower_bound, upper_bound).into()
+    }
+
+    /// How members of closing guilds exit when unstaking. Defaults to DirectPayout.
+    #[endpoint(setClosedGuildExitMode)]
+    fn set_closed_guild_exit_mode(&self, exit_mode: ClosedGuildExitMode) {
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(exit_mode);
+        self.require_change_unlocked(b"setClosedGuildExitMode", args);
+
+        let old_exit_mode = self.closed_guild_exit_mode().get();
+        self.closed_guild_exit_mode().set(exit_mode);
+
+        self.emit_closed_guild_exit_mode_change(old_exit_mode, exit_mode);
+    }
+
+    /// Voting period and quorum of the proposals created by guild members.
+    /// The quorum is a percentage of the total member stake when the proposal is created.
+    #[endpoint(setGovernanceSettings)]
+    fn set_governance_settings(&self, voting_period_epochs: Epoch, quorum: Percent) {
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(voting_period_epochs);
+        args.push_arg(quorum);
+        self.require_change_unlocked(b"setGovernanceSettings", args);
+
+        require!(voting_period_epochs > 0, INVALID_VALUE_ERR_MSG);
+        require!(quorum > 0 && quorum <= MAX_PERCENT, INVALID_VALUE_ERR_MSG);
+
+        let old_settings = self.get_governance_settings().into_tuple();
+        self.proposal_voting_period_epochs()
+            .set(voting_period_epochs);
+        self.proposal_quorum().set(quorum);
+
+        self.emit_governance_settings_change(old_settings, (voting_period_epochs, quorum));
+    }
+
+    /// Defaults to DEFAULT_PROPOSAL_VOTING_PERIOD_EPOCHS and DEFAULT_PROPOSAL_QUORUM
+    #[view(getGovernanceSettings)]
+    fn get_governance_settings(&self) -> MultiValue2<Epoch, Percent> {
+        let voting_period_mapper = self.proposal_voting_period_epochs();
+        let voting_period_epochs = if !voting_period_mapper.is_empty() {
+            voting_period_mapper.get()
+        } else {
+            DEFAULT_PROPOSAL_VOTING_PERIOD_EPOCHS
+        };
+
+        let quorum_mapper = self.proposal_quorum();
+        let quorum = if !quorum_mapper.is_empty() {
+            quorum_mapper.get()
+        } else {
+            DEFAULT_PROPOSAL_QUORUM
+        };
+
+        (voting_period_epochs, quorum).into()
+    }
+
+    /// Fee taken from all the rewards produced by the guilds, in basis points.
+    /// The fees are withdrawn to the protocol treasury through the factory.
+    #[endpoint(setProtocolFee)]
+    fn set_protocol_fee(&self, fee: Percent) {
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(fee);
+        self.require_change_unlocked(b"setProtocolFee", args);
+
+        require!(fee <= MAX_PROTOCOL_FEE, INVALID_VALUE_ERR_MSG);
+
+        let old_fee = self.protocol_fee().get();
+        self.protocol_fee().set(fee);
+
+        self.emit_protocol_fee_change(old_fee, fee);
+    }
+
+    /// Guilds stop producing rewards once their guild master's stake has been below `min_stake_guild_master`,
+    /// or below `collateral_ratio` of the guild's total stake, for `grace_epochs` epochs.
+    /// A zero collateral ratio only checks the min stake.
+    #[endpoint(setCollateralSettings)]
+    fn set_collateral_settings(&self, collateral_ratio: Percent, grace_epochs: Epoch) {
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(collateral_ratio);
+        args.push_arg(grace_epochs);
+        self.require_change_unlocked(b"setCollateralSettings", args);
+
+        require!(collateral_ratio <= MAX_PERCENT, INVALID_VALUE_ERR_MSG);
+        require!(grace_epochs > 0, INVALID_VALUE_ERR_MSG);
+
+        let old_settings = self.get_collateral_settings().into_tuple();
+        self.collateral_ratio().set(collateral_ratio);
+        self.undercollateralized_grace_epochs().set(grace_epochs);
+
+        self.emit_collateral_settings_change(old_settings, (collateral_ratio, grace_epochs));
+    }
+
+    /// Zero grace epochs means the guild master's collateral is not checked
+    #[view(getCollateralSettings)]
+    fn get_collateral_settings(&self) -> MultiValue2<Percent, Epoch> {
+        let collateral_ratio = self.collateral_ratio().get();
+        let grace_epochs = self.undercollateralized_grace_epochs().get();
+
+        (collateral_ratio, grace_epochs).into()
+    }
+
+    /// Pair used by the guilds to swap the given token to the farming token
+    #[endpoint(setSwapPair)]
+    fn set_swap_pair(&self, token_id: TokenIdentifier, pair_address: ManagedAddress) {
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(&token_id);
+        args.push_arg(&pair_address);
+        self.require_change_unlocked(b"setSwapPair", args);
+
+        require!(token_id.is_valid_esdt_identifier(), INVALID_VALUE_ERR_MSG);
+        require!(
+            self.blockchain().is_smart_contract(&pair_address),
+            "Invalid pair address"
+        );
+
+        let old_pair_address = self.get_swap_pair(token_id.clone());
+        self.swap_pair(&token_id).set(&pair_address);
+
+        self.emit_swap_pair_change(&token_id, &old_pair_address, &pair_address);
+    }
+
+    #[endpoint(removeSwapPair)]
+    fn remove_swap_pair(&self, token_id: TokenIdentifier) {
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(&token_id);
+        self.require_change_unlocked(b"removeSwapPair", args);
+
+        let mapper = self.swap_pair(&token_id);
+        require!(!mapper.is_empty(), NO_SWAP_PAIR_ERR_MSG);
+
+        let old_pair_address = mapper.take();
+
+        self.emit_swap_pair_change(&token_id, &old_pair_address, &ManagedAddress::zero());
+    }
+
+    /// Returns the zero address if no pair is set for the token
+    #[view(getSwapPair)]
+    fn get_swap_pair(&self, token_id: TokenIdentifier) -> ManagedAddress {
+        let mapper = self.swap_pair(&token_id);
+        if !mapper.is_empty() {
+            mapper.get()
+        } else {
+            ManagedAddress::zero()
+        }
+    }
+
     fn require_guild_sc_caller(&self) {
         let caller = self.blockchain().get_caller();
         let factory_sc = self.blockchain().get_owner_address();
@@ -124,6 +538,32 @@ pub trait GlobalConfigModule {
     #[storage_mapper("minUnbondEpochsGuildMaster")]
     fn min_unbond_epochs_guild_master(&self) -> SingleValueMapper<Epoch>;
 
+    #[storage_mapper("unbondEpochsLowerBound")]
+    fn unbond_epochs_lower_bound(&self) -> SingleValueMapper<Epoch>;
+
+    #[storage_mapper("unbondEpochsUpperBound")]
+    fn unbond_epochs_upper_bound(&self) -> SingleValueMapper<Epoch>;
+
+    #[view(getClosedGuildExitMode)]
+    #[storage_mapper("closedGuildExitMode")]
+    fn closed_guild_exit_mode(&self) -> SingleValueMapper<ClosedGuildExitMode>;
+
+    #[storage_mapper("proposalVotingPeriodEpochs")]
+    fn proposal_voting_period_epochs(&self) -> SingleValueMapper<Epoch>;
+
+    #[storage_mapper("proposalQuorum")]
+    fn proposal_quorum(&self) -> SingleValueMapper<Percent>;
+
+    #[view(getProtocolFee)]
+    #[storage_mapper("protocolFee")]
+    fn protocol_fee(&self) -> SingleValueMapper<Percent>;
+
+    #[storage_mapper("collateralRatio")]
+    fn collateral_ratio(&self) -> SingleValueMapper<Percent>;
+
+    #[storage_mapper("undercollateralizedGraceEpochs")]
+    fn undercollateralized_grace_epochs(&self) -> SingleValueMapper<Epoch>;
+
     #[view(getMinStakeUser)]
     #[storage_mapper("minStakeUser")]
     fn min_stake_user(&self) -> SingleValueMapper<BigUint>;
@@ -140,6 +580,14 @@ pub trait GlobalConfigModule {
     #[storage_mapper("totalStakingTokenStaked")]
     fn total_staking_token_staked(&self) -> SingleValueMapper<BigUint>;
 
+    #[view(getStakedPercentAverage)]
+    #[storage_mapper("stakedPercentAverage")]
+    fn staked_percent_average(&self) -> SingleValueMapper<StakedPercentAverage>;
+
+    #[view(getGlobalStakingCap)]
+    #[storage_mapper("globalStakingCap")]
+    fn global_staking_cap(&self) -> SingleValueMapper<BigUint>;
+
     #[view(getBaseFarmTokenId)]
     #[storage_mapper("baseFarmTokenId")]
     fn base_farm_token_id(&self) -> SingleValueMapper<ManagedBuffer>;
@@ -164,6 +612,9 @@ pub trait GlobalConfigModule {
     #[storage_mapper("perBlockRewardAmount")]
     fn per_block_reward_amount(&self) -> SingleValueMapper<BigUint>;
 
+    #[storage_mapper("swapPair")]
+    fn swap_pair(&self, token_id: &TokenIdentifier) -> SingleValueMapper<ManagedAddress>;
+
     #[view(areAllGuildsPaused)]
     #[storage_mapper("globalPauseStatus")]
     fn global_pause_status(&self) -> SingleValueMapper<GlobalPauseStatus>;
@@ -5,9 +5,13 @@ use common_structs::Epoch;
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+pub mod boosted_yields;
+pub mod config_events;
 pub mod global_config;
+pub mod roles;
 pub mod tier_types;
 pub mod tiers;
+pub mod timelock;
 
 #[derive(TypeAbi, TopEncode, TopDecode)]
 pub struct InitArgs<M: ManagedTypeApi> {
@@ -26,7 +30,14 @@ pub struct InitArgs<M: ManagedTypeApi> {
 }
 
 #[multiversx_sc::contract]
-pub trait GuildScConfig: tiers::TierModule + global_config::GlobalConfigModule {
+pub trait GuildScConfig:
+    tiers::TierModule
+    + global_config::GlobalConfigModule
+    + timelock::TimelockModule
+    + roles::RolesModule
+    + config_events::ConfigEventsModule
+    + boosted_yields::BoostedYieldsConfigModule
+{
     #[init]
     fn init(&self, args: InitArgs<Self::Api>) {
         self.set_total_staking_token_minted(args.total_staking_tokens_minted);
@@ -46,5 +57,7 @@ pub trait GuildScConfig: tiers::TierModule + global_config::GlobalConfigModule {
     }
 
     #[upgrade]
-    fn upgrade(&self) {}
+    fn upgrade(&self) {
+        self.update_staked_percent_average();
+    }
 }
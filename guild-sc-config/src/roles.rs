@@ -0,0 +1,75 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub static PERMISSION_DENIED_ERR_MSG: &[u8] = b"Permission denied";
+
+/// The owner (i.e. the factory SC) has all the roles.
+/// SuperAdmin may call any setter, and manages the other roles.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum ConfigRole {
+    SuperAdmin,
+    TierManager,
+    EmissionsManager,
+    Pauser,
+}
+
+#[multiversx_sc::module]
+pub trait RolesModule {
+    #[endpoint(grantRole)]
+    fn grant_role(&self, role: ConfigRole, address: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        require!(self.is_super_admin(&caller), PERMISSION_DENIED_ERR_MSG);
+
+        let added = self.role_members(role).insert(address.clone());
+        require!(added, "Address already has this role");
+
+        self.grant_role_event(role, &address, &caller);
+    }
+
+    #[endpoint(revokeRole)]
+    fn revoke_role(&self, role: ConfigRole, address: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        require!(self.is_super_admin(&caller), PERMISSION_DENIED_ERR_MSG);
+
+        let removed = self.role_members(role).swap_remove(&address);
+        require!(removed, "Address does not have this role");
+
+        self.revoke_role_event(role, &address, &caller);
+    }
+
+    #[view(hasRole)]
+    fn has_role(&self, role: ConfigRole, address: ManagedAddress) -> bool {
+        self.is_super_admin(&address) || self.role_members(role).contains(&address)
+    }
+
+    fn require_caller_has_role(&self, role: ConfigRole) {
+        let caller = self.blockchain().get_caller();
+        require!(self.has_role(role, caller), PERMISSION_DENIED_ERR_MSG);
+    }
+
+    fn is_super_admin(&self, address: &ManagedAddress) -> bool {
+        let owner = self.blockchain().get_owner_address();
+
+        address == &owner || self.role_members(ConfigRole::SuperAdmin).contains(address)
+    }
+
+    #[event("grantRole")]
+    fn grant_role_event(
+        &self,
+        #[indexed] role: ConfigRole,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] granted_by: &ManagedAddress,
+    );
+
+    #[event("revokeRole")]
+    fn revoke_role_event(
+        &self,
+        #[indexed] role: ConfigRole,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] revoked_by: &ManagedAddress,
+    );
+
+    #[view(getRoleMembers)]
+    #[storage_mapper("roleMembers")]
+    fn role_members(&self, role: ConfigRole) -> UnorderedSetMapper<ManagedAddress>;
+}
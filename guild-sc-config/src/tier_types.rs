@@ -8,7 +8,15 @@ multiversx_sc::derive_imports!();
 pub type GuildMasterRewardTierMultiValue<M> = MultiValue2<BigUint<M>, Percent>;
 pub type UserRewardTierMultiValue = MultiValue2<Percent, Percent>;
 
-#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+/// Step: the APR of the first tier containing the value is used.
+/// Linear: the APR is linearly interpolated between the breakpoints of the neighbouring tiers.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum TierCurveMode {
+    Step,
+    Linear,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem)]
 pub struct GuildMasterRewardTier<M: ManagedTypeApi> {
     pub max_stake: BigUint<M>,
     pub apr: Percent,
@@ -25,7 +33,7 @@ impl<M: ManagedTypeApi> From<GuildMasterRewardTierMultiValue<M>> for GuildMaster
     }
 }
 
-#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem)]
 pub struct UserRewardTier {
     pub max_percentage_staked: Percent,
     pub apr: Percent,
@@ -43,6 +51,10 @@ impl From<UserRewardTierMultiValue> for UserRewardTier {
 }
 
 pub trait RewardTier<M: ManagedTypeApi> {
+    fn get_position(user_stake: &BigUint<M>, percentage_staked: Percent) -> BigUint<M>;
+
+    fn get_breakpoint(&self) -> BigUint<M>;
+
     fn is_in_range(&self, user_stake: &BigUint<M>, percentage_staked: Percent) -> bool;
 
     fn is_below(&self, other: &Self) -> bool;
@@ -55,6 +67,14 @@ pub trait RewardTier<M: ManagedTypeApi> {
 }
 
 impl<M: ManagedTypeApi> RewardTier<M> for GuildMasterRewardTier<M> {
+    fn get_position(user_stake: &BigUint<M>, _percentage_staked: Percent) -> BigUint<M> {
+        user_stake.clone()
+    }
+
+    fn get_breakpoint(&self) -> BigUint<M> {
+        self.max_stake.clone()
+    }
+
     fn is_in_range(&self, user_stake: &BigUint<M>, _percentage_staked: Percent) -> bool {
         user_stake <= &self.max_stake
     }
@@ -77,6 +97,14 @@ impl<M: ManagedTypeApi> RewardTier<M> for GuildMasterRewardTier<M> {
 }
 
 impl<M: ManagedTypeApi> RewardTier<M> for UserRewardTier {
+    fn get_position(_user_stake: &BigUint<M>, percentage_staked: Percent) -> BigUint<M> {
+        BigUint::from(percentage_staked)
+    }
+
+    fn get_breakpoint(&self) -> BigUint<M> {
+        BigUint::from(self.max_percentage_staked)
+    }
+
     fn is_in_range(&self, _user_stake: &BigUint<M>, percentage_staked: Percent) -> bool {
         percentage_staked <= self.max_percentage_staked
     }
@@ -97,3 +125,32 @@ impl<M: ManagedTypeApi> RewardTier<M> for UserRewardTier {
         self.apr = other.apr;
     }
 }
+
+/// Expects `lower_tier` to be below `upper_tier`, with a lower or equal APR,
+/// and `position` to be between the two breakpoints
+pub fn interpolate_apr<M: ManagedTypeApi, T: RewardTier<M>>(
+    lower_tier: &T,
+    upper_tier: &T,
+    position: &BigUint<M>,
+) -> Percent {
+    let lower_breakpoint = lower_tier.get_breakpoint();
+    let upper_breakpoint = upper_tier.get_breakpoint();
+    let lower_apr = lower_tier.get_apr();
+    let upper_apr = upper_tier.get_apr();
+    if position <= &lower_breakpoint || upper_apr <= lower_apr {
+        return lower_apr;
+    }
+    if position >= &upper_breakpoint {
+        return upper_apr;
+    }
+
+    let apr_diff = BigUint::<M>::from(upper_apr - lower_apr);
+    let apr_increase =
+        apr_diff * (position - &lower_breakpoint) / (upper_breakpoint - lower_breakpoint);
+    let opt_apr_increase = apr_increase.to_u64();
+    if opt_apr_increase.is_none() {
+        M::error_api_impl().signal_error(INVALID_APR_ERR_MSG);
+    }
+
+    lower_apr + unsafe { opt_apr_increase.unwrap_unchecked() }
+}
@@ -1,8 +1,9 @@
 use common_structs::Percent;
 
+use crate::roles::ConfigRole;
 use crate::tier_types::{
-    GuildMasterRewardTier, GuildMasterRewardTierMultiValue, RewardTier, UserRewardTier,
-    UserRewardTierMultiValue,
+    GuildMasterRewardTier, GuildMasterRewardTierMultiValue, RewardTier, TierCurveMode,
+    UserRewardTier, UserRewardTierMultiValue,
 };
 
 multiversx_sc::imports!();
@@ -17,9 +18,14 @@ pub const FIRST_INDEX_VEC_MAPPER: usize = 1;
 
 #[multiversx_sc::module]
 pub trait TierModule: crate::global_config::GlobalConfigModule {
-    #[only_owner]
     #[endpoint(setMaxStakedTokens)]
     fn set_max_staked_tokens(&self, max_staked_tokens: BigUint) {
+        self.require_caller_has_role(ConfigRole::TierManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(&max_staked_tokens);
+        self.require_change_unlocked(b"setMaxStakedTokens", args);
+
         let tiers_mapper = self.guild_master_tiers();
         let tiers_len = tiers_mapper.len();
         if tiers_len > 1 {
@@ -30,83 +36,160 @@ pub trait TierModule: crate::global_config::GlobalConfigModule {
             );
         }
 
-        self.max_staked_tokens().set(max_staked_tokens);
+        let old_max_staked_tokens = self.max_staked_tokens().get();
+        self.max_staked_tokens().set(&max_staked_tokens);
+
+        self.emit_max_staked_tokens_change(&old_max_staked_tokens, &max_staked_tokens);
     }
 
     /// Pairs of (max_stake, apr)
     /// APR is scaled by two decimals, i.e. 10_000 is 100%
     /// Last max_stake value must be equal to the init value of max_staked_tokens
-    #[only_owner]
     #[endpoint(addGuildMasterTiers)]
     fn add_guild_master_tiers(
         &self,
         tiers: MultiValueEncoded<GuildMasterRewardTierMultiValue<Self::Api>>,
     ) {
+        self.require_caller_has_role(ConfigRole::TierManager);
+
         let mut tiers_mapper = self.guild_master_tiers();
         self.require_empty_mapper(&tiers_mapper);
 
         self.set_guild_master_tiers_common(&mut tiers_mapper, tiers);
+
+        self.emit_guild_master_tiers_change(ManagedVec::new(), self.collect_tiers(&tiers_mapper));
     }
 
     /// Pairs of (max_stake, apr)
     /// APR is scaled by two decimals, i.e. 10_000 is 100%
     /// Last max_stake value must be equal to the init value of max_staked_tokens
-    #[only_owner]
     #[endpoint(updateGuildMasterTiers)]
     fn update_guild_master_tiers(
         &self,
         tiers: MultiValueEncoded<GuildMasterRewardTierMultiValue<Self::Api>>,
     ) {
+        self.require_caller_has_role(ConfigRole::TierManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_multi_arg(&tiers);
+        self.require_change_unlocked(b"updateGuildMasterTiers", args);
+
         let mut tiers_mapper = self.guild_master_tiers();
+        let old_tiers = self.collect_tiers(&tiers_mapper);
         tiers_mapper.clear();
 
         self.set_guild_master_tiers_common(&mut tiers_mapper, tiers);
+
+        self.emit_guild_master_tiers_change(old_tiers, self.collect_tiers(&tiers_mapper));
     }
 
-    #[only_owner]
     #[endpoint(setGuildMasterTierApr)]
     fn set_guild_master_tier_apr(&self, max_stake: BigUint, new_apr: Percent) {
+        self.require_caller_has_role(ConfigRole::TierManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(&max_stake);
+        args.push_arg(new_apr);
+        self.require_change_unlocked(b"setGuildMasterTierApr", args);
+
         let mut tiers_mapper = self.guild_master_tiers();
         let reward_tier = GuildMasterRewardTier {
-            max_stake,
+            max_stake: max_stake.clone(),
             apr: new_apr,
         };
-        self.set_apr(&mut tiers_mapper, reward_tier);
+        let curve_mode = self.guild_master_tiers_curve_mode().get();
+        let old_apr = self.set_apr(&mut tiers_mapper, reward_tier, curve_mode);
+
+        self.emit_guild_master_tier_apr_change(&max_stake, old_apr, new_apr);
     }
 
     /// Pairs of (max_percentage_staked, apr)
     /// Both percentages are scaled by two decimals, i.e. 10_000 is 100%
     /// max_percentage_staked must be <= 10_000, and the last one must be 10_000
-    #[only_owner]
     #[endpoint(addUserTiers)]
     fn add_user_tiers(&self, tiers: MultiValueEncoded<UserRewardTierMultiValue>) {
+        self.require_caller_has_role(ConfigRole::TierManager);
+
         let mut tiers_mapper = self.user_tiers();
         self.require_empty_mapper(&tiers_mapper);
 
         self.set_user_tiers_common(&mut tiers_mapper, tiers);
+
+        self.emit_user_tiers_change(ManagedVec::new(), self.collect_tiers(&tiers_mapper));
     }
 
     /// Pairs of (max_percentage_staked, apr)
     /// Both percentages are scaled by two decimals, i.e. 10_000 is 100%
     /// max_percentage_staked must be <= 10_000, and the last one must be 10_000
-    #[only_owner]
     #[endpoint(updateUserTiers)]
     fn update_user_tiers(&self, tiers: MultiValueEncoded<UserRewardTierMultiValue>) {
+        self.require_caller_has_role(ConfigRole::TierManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_multi_arg(&tiers);
+        self.require_change_unlocked(b"updateUserTiers", args);
+
         let mut tiers_mapper = self.user_tiers();
+        let old_tiers = self.collect_tiers(&tiers_mapper);
         tiers_mapper.clear();
 
         self.set_user_tiers_common(&mut tiers_mapper, tiers);
+
+        self.emit_user_tiers_change(old_tiers, self.collect_tiers(&tiers_mapper));
     }
 
-    #[only_owner]
     #[endpoint(setUserTierApr)]
     fn set_user_tier_apr(&self, max_percentage_staked: Percent, new_apr: Percent) {
+        self.require_caller_has_role(ConfigRole::TierManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(max_percentage_staked);
+        args.push_arg(new_apr);
+        self.require_change_unlocked(b"setUserTierApr", args);
+
         let mut tiers_mapper = self.user_tiers();
         let reward_tier = UserRewardTier {
             max_percentage_staked,
             apr: new_apr,
         };
-        self.set_apr(&mut tiers_mapper, reward_tier);
+        let curve_mode = self.user_tiers_curve_mode().get();
+        let old_apr = self.set_apr(&mut tiers_mapper, reward_tier, curve_mode);
+
+        self.emit_user_tier_apr_change(max_percentage_staked, old_apr, new_apr);
+    }
+
+    /// Step uses the APR of the matching tier, Linear interpolates between tier breakpoints
+    #[endpoint(setGuildMasterTiersCurveMode)]
+    fn set_guild_master_tiers_curve_mode(&self, curve_mode: TierCurveMode) {
+        self.require_caller_has_role(ConfigRole::TierManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(curve_mode);
+        self.require_change_unlocked(b"setGuildMasterTiersCurveMode", args);
+
+        self.require_valid_apr_progression(&self.guild_master_tiers(), curve_mode);
+
+        let old_curve_mode = self.guild_master_tiers_curve_mode().get();
+        self.guild_master_tiers_curve_mode().set(curve_mode);
+
+        self.emit_guild_master_tiers_curve_mode_change(old_curve_mode, curve_mode);
+    }
+
+    /// Step uses the APR of the matching tier, Linear interpolates between tier breakpoints
+    #[endpoint(setUserTiersCurveMode)]
+    fn set_user_tiers_curve_mode(&self, curve_mode: TierCurveMode) {
+        self.require_caller_has_role(ConfigRole::TierManager);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(curve_mode);
+        self.require_change_unlocked(b"setUserTiersCurveMode", args);
+
+        self.require_valid_apr_progression(&self.user_tiers(), curve_mode);
+
+        let old_curve_mode = self.user_tiers_curve_mode().get();
+        self.user_tiers_curve_mode().set(curve_mode);
+
+        self.emit_user_tiers_curve_mode_change(old_curve_mode, curve_mode);
     }
 
     fn set_guild_master_tiers_common(
@@ -114,10 +197,11 @@ pub trait TierModule: crate::global_config::GlobalConfigModule {
         tiers_mapper: &mut VecMapper<GuildMasterRewardTier<Self::Api>>,
         tiers: MultiValueEncoded<GuildMasterRewardTierMultiValue<Self::Api>>,
     ) {
+        let curve_mode = self.guild_master_tiers_curve_mode().get();
         let tiers_len = tiers.len();
         for (i, tier_multi) in tiers.into_iter().enumerate() {
             let reward_tier = GuildMasterRewardTier::from(tier_multi);
-            self.add_tier(tiers_mapper, &reward_tier);
+            self.add_tier(tiers_mapper, &reward_tier, curve_mode);
 
             if i == tiers_len - 1 {
                 let max_staked_tokens = self.max_staked_tokens().get();
@@ -134,12 +218,13 @@ pub trait TierModule: crate::global_config::GlobalConfigModule {
         tiers_mapper: &mut VecMapper<UserRewardTier>,
         tiers: MultiValueEncoded<UserRewardTierMultiValue>,
     ) {
+        let curve_mode = self.user_tiers_curve_mode().get();
         let tiers_len = tiers.len();
         for (i, tier_multi) in tiers.into_iter().enumerate() {
             let reward_tier = UserRewardTier::from(tier_multi);
             self.require_valid_user_tier(&reward_tier);
 
-            self.add_tier(tiers_mapper, &reward_tier);
+            self.add_tier(tiers_mapper, &reward_tier, curve_mode);
 
             if i == tiers_len - 1 {
                 require!(
@@ -154,6 +239,7 @@ pub trait TierModule: crate::global_config::GlobalConfigModule {
         &self,
         mapper: &mut VecMapper<T>,
         tier: &T,
+        curve_mode: TierCurveMode,
     ) {
         let mapper_len = mapper.len();
         require!(mapper_len < MAX_TIERS, "May not add more tiers");
@@ -161,20 +247,19 @@ pub trait TierModule: crate::global_config::GlobalConfigModule {
         if mapper_len > 0 {
             let previous_entry = mapper.get(mapper_len);
             require!(previous_entry.is_below(tier), "Invalid stake entry");
-            require!(
-                tier.get_apr() > previous_entry.get_apr(),
-                INVALID_APR_ERR_MSG
-            );
+            self.require_valid_apr_step(previous_entry.get_apr(), tier.get_apr(), curve_mode);
         }
 
         mapper.push(tier);
     }
 
+    /// Returns the previous APR of the tier
     fn set_apr<T: TopEncode + TopDecode + RewardTier<Self::Api>>(
         &self,
         mapper: &mut VecMapper<T>,
         reward_tier: T,
-    ) {
+        curve_mode: TierCurveMode,
+    ) -> Percent {
         let mut opt_found_index = None;
         for (i, tier) in mapper.iter().enumerate() {
             if tier.is_equal(&reward_tier) {
@@ -189,25 +274,59 @@ pub trait TierModule: crate::global_config::GlobalConfigModule {
         if index > FIRST_INDEX_VEC_MAPPER {
             let prev_index = index - 1;
             let prev_entry = mapper.get(prev_index);
-            require!(
-                reward_tier.get_apr() > prev_entry.get_apr(),
-                INVALID_APR_ERR_MSG
-            );
+            self.require_valid_apr_step(prev_entry.get_apr(), reward_tier.get_apr(), curve_mode);
         }
 
         let last_index_vec_mapper = mapper.len();
         if index < last_index_vec_mapper {
             let next_index = index + 1;
             let next_entry = mapper.get(next_index);
-            require!(
-                reward_tier.get_apr() < next_entry.get_apr(),
-                INVALID_APR_ERR_MSG
-            );
+            self.require_valid_apr_step(reward_tier.get_apr(), next_entry.get_apr(), curve_mode);
         }
 
         let mut tier = mapper.get(index);
+        let old_apr = tier.get_apr();
         tier.set_apr(&reward_tier);
         mapper.set(index, &tier);
+
+        old_apr
+    }
+
+    fn collect_tiers<T: TopEncode + TopDecode + ManagedVecItem + RewardTier<Self::Api>>(
+        &self,
+        mapper: &VecMapper<T>,
+    ) -> ManagedVec<T> {
+        mapper.iter().collect()
+    }
+
+    fn require_valid_apr_progression<T: TopEncode + TopDecode + RewardTier<Self::Api>>(
+        &self,
+        mapper: &VecMapper<T>,
+        curve_mode: TierCurveMode,
+    ) {
+        let mut opt_previous_apr = None;
+        for tier in mapper.iter() {
+            if let Some(previous_apr) = opt_previous_apr {
+                self.require_valid_apr_step(previous_apr, tier.get_apr(), curve_mode);
+            }
+
+            opt_previous_apr = Some(tier.get_apr());
+        }
+    }
+
+    /// Step tiers must have strictly increasing APRs, to avoid duplicate tiers.
+    /// Linear breakpoints may also keep the same APR.
+    fn require_valid_apr_step(
+        &self,
+        lower_tier_apr: Percent,
+        upper_tier_apr: Percent,
+        curve_mode: TierCurveMode,
+    ) {
+        let is_valid = match curve_mode {
+            TierCurveMode::Step => upper_tier_apr > lower_tier_apr,
+            TierCurveMode::Linear => upper_tier_apr >= lower_tier_apr,
+        };
+        require!(is_valid, INVALID_APR_ERR_MSG);
     }
 
     fn require_valid_user_tier(&self, user_reward_tier: &UserRewardTier) {
@@ -236,4 +355,12 @@ pub trait TierModule: crate::global_config::GlobalConfigModule {
     #[view(getUserTiers)]
     #[storage_mapper("userTiers")]
     fn user_tiers(&self) -> VecMapper<UserRewardTier>;
+
+    #[view(getGuildMasterTiersCurveMode)]
+    #[storage_mapper("guildMasterTiersCurveMode")]
+    fn guild_master_tiers_curve_mode(&self) -> SingleValueMapper<TierCurveMode>;
+
+    #[view(getUserTiersCurveMode)]
+    #[storage_mapper("userTiersCurveMode")]
+    fn user_tiers_curve_mode(&self) -> SingleValueMapper<TierCurveMode>;
 }
@@ -0,0 +1,262 @@
+use common_structs::Epoch;
+
+use crate::roles::{ConfigRole, PERMISSION_DENIED_ERR_MSG};
+
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub type ChangeId = u64;
+
+static UNKNOWN_CHANGE_ERR_MSG: &[u8] = b"Unknown config change";
+
+pub const MAX_TIMELOCK_EPOCHS: Epoch = 30;
+pub const CONFIG_CHANGE_EXPIRY_EPOCHS: Epoch = 30;
+
+/// Setters which go through `require_change_unlocked`, and the role needed to propose changes to them
+pub static TIMELOCKED_SETTERS: &[(&[u8], ConfigRole)] = &[
+    (b"setTimelockEpochs", ConfigRole::SuperAdmin),
+    (b"setMinStakeUser", ConfigRole::SuperAdmin),
+    (b"setMinStakeGuildMaster", ConfigRole::SuperAdmin),
+    (b"setMinUnbondEpochsUser", ConfigRole::SuperAdmin),
+    (b"setMinUnbondEpochsGuildMaster", ConfigRole::SuperAdmin),
+    (b"setUnbondEpochsBounds", ConfigRole::SuperAdmin),
+    (b"setClosedGuildExitMode", ConfigRole::SuperAdmin),
+    (b"setGovernanceSettings", ConfigRole::SuperAdmin),
+    (b"setProtocolFee", ConfigRole::SuperAdmin),
+    (b"setCollateralSettings", ConfigRole::SuperAdmin),
+    (b"setSwapPair", ConfigRole::SuperAdmin),
+    (b"removeSwapPair", ConfigRole::SuperAdmin),
+    (b"setEnergyFactoryAddress", ConfigRole::SuperAdmin),
+    (b"setTotalStakingTokenMinted", ConfigRole::EmissionsManager),
+    (b"setGlobalStakingCap", ConfigRole::EmissionsManager),
+    (b"setSecondsPerBlock", ConfigRole::EmissionsManager),
+    (b"setPerBlockRewardAmount", ConfigRole::EmissionsManager),
+    (b"setBoostedYieldsFactors", ConfigRole::EmissionsManager),
+    (
+        b"setBoostedYieldsRewardsPercentage",
+        ConfigRole::EmissionsManager,
+    ),
+    (b"setMaxStakedTokens", ConfigRole::TierManager),
+    (b"updateGuildMasterTiers", ConfigRole::TierManager),
+    (b"setGuildMasterTierApr", ConfigRole::TierManager),
+    (b"updateUserTiers", ConfigRole::TierManager),
+    (b"setUserTierApr", ConfigRole::TierManager),
+    (b"setGuildMasterTiersCurveMode", ConfigRole::TierManager),
+    (b"setUserTiersCurveMode", ConfigRole::TierManager),
+];
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct PendingConfigChange<M: ManagedTypeApi> {
+    pub proposer: ManagedAddress<M>,
+    pub function_name: ManagedBuffer<M>,
+    pub args: ManagedVec<M, ManagedBuffer<M>>,
+    pub proposal_epoch: Epoch,
+    pub executable_epoch: Epoch,
+}
+
+#[multiversx_sc::module]
+pub trait TimelockModule:
+    crate::roles::RolesModule + crate::config_events::ConfigEventsModule
+{
+    /// Announces a call to one of the timelocked setters, which the caller's role may call.
+    /// Once `executable_epoch` is reached, the setter may be called with the exact same arguments,
+    /// for CONFIG_CHANGE_EXPIRY_EPOCHS epochs.
+    /// While the timelock is 0, setters take effect immediately and no proposal is needed.
+    #[endpoint(proposeConfigChange)]
+    fn propose_config_change(
+        &self,
+        function_name: ManagedBuffer,
+        args: MultiValueEncoded<ManagedBuffer>,
+    ) -> ChangeId {
+        let opt_role = self.get_timelocked_setter_role(&function_name);
+        require!(opt_role.is_some(), "Unknown timelocked setter");
+
+        let role = unsafe { opt_role.unwrap_unchecked() };
+        self.require_caller_has_role(role);
+
+        let args = args.to_vec();
+        let change_id_mapper = self.pending_change_id(&function_name, &args);
+        require!(
+            change_id_mapper.is_empty(),
+            "Config change already proposed"
+        );
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let change = PendingConfigChange {
+            proposer: self.blockchain().get_caller(),
+            function_name,
+            args,
+            proposal_epoch: current_epoch,
+            executable_epoch: current_epoch + self.timelock_epochs().get(),
+        };
+
+        let change_id = self.last_change_id().update(|id| {
+            *id += 1;
+            *id
+        });
+        self.pending_change(change_id).set(&change);
+        let _ = self.pending_change_ids().insert(change_id);
+        change_id_mapper.set(change_id);
+
+        self.propose_config_change_event(change_id, change.executable_epoch, &change);
+
+        change_id
+    }
+
+    /// May be called by a super admin, or by the address that proposed the change.
+    /// Expired changes may be removed by anyone.
+    #[endpoint(cancelConfigChange)]
+    fn cancel_config_change(&self, change_id: ChangeId) {
+        let change_mapper = self.pending_change(change_id);
+        require!(!change_mapper.is_empty(), UNKNOWN_CHANGE_ERR_MSG);
+
+        let change = change_mapper.get();
+        let caller = self.blockchain().get_caller();
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(
+            caller == change.proposer
+                || self.is_super_admin(&caller)
+                || self.is_change_expired(&change, current_epoch),
+            PERMISSION_DENIED_ERR_MSG
+        );
+
+        self.remove_pending_change(change_id, &change);
+
+        self.cancel_config_change_event(change_id);
+    }
+
+    /// Changing the timelock is itself timelocked, so the delay can't be lowered without notice
+    #[endpoint(setTimelockEpochs)]
+    fn set_timelock_epochs(&self, timelock_epochs: Epoch) {
+        self.require_caller_has_role(ConfigRole::SuperAdmin);
+
+        let mut args = ManagedArgBuffer::new();
+        args.push_arg(timelock_epochs);
+        self.require_change_unlocked(b"setTimelockEpochs", args);
+
+        require!(
+            timelock_epochs <= MAX_TIMELOCK_EPOCHS,
+            "Invalid timelock epochs"
+        );
+
+        let old_timelock_epochs = self.timelock_epochs().get();
+        self.timelock_epochs().set(timelock_epochs);
+
+        self.emit_timelock_epochs_change(old_timelock_epochs, timelock_epochs);
+    }
+
+    /// Consumes the matching pending change, if the timelock is active
+    fn require_change_unlocked(&self, function_name: &[u8], args: ManagedArgBuffer<Self::Api>) {
+        if self.timelock_epochs().get() == 0 {
+            return;
+        }
+
+        let function_name = ManagedBuffer::new_from_bytes(function_name);
+        let args = args.into_vec_of_buffers();
+        let change_id_mapper = self.pending_change_id(&function_name, &args);
+        require!(
+            !change_id_mapper.is_empty(),
+            "Config change was not proposed"
+        );
+
+        let change_id = change_id_mapper.get();
+        let change = self.pending_change(change_id).get();
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(
+            change.executable_epoch <= current_epoch,
+            "Config change still timelocked"
+        );
+        require!(
+            !self.is_change_expired(&change, current_epoch),
+            "Config change expired"
+        );
+
+        self.remove_pending_change(change_id, &change);
+
+        self.execute_config_change_event(change_id, &change);
+    }
+
+    fn remove_pending_change(&self, change_id: ChangeId, change: &PendingConfigChange<Self::Api>) {
+        let _ = self.pending_change_ids().swap_remove(&change_id);
+        self.pending_change(change_id).clear();
+        self.pending_change_id(&change.function_name, &change.args)
+            .clear();
+    }
+
+    fn is_change_expired(
+        &self,
+        change: &PendingConfigChange<Self::Api>,
+        current_epoch: Epoch,
+    ) -> bool {
+        current_epoch > change.executable_epoch + CONFIG_CHANGE_EXPIRY_EPOCHS
+    }
+
+    fn get_timelocked_setter_role(&self, function_name: &ManagedBuffer) -> Option<ConfigRole> {
+        TIMELOCKED_SETTERS
+            .iter()
+            .find(|(name, _)| function_name == &ManagedBuffer::new_from_bytes(name))
+            .map(|(_, role)| *role)
+    }
+
+    #[view(getPendingConfigChanges)]
+    fn get_pending_config_changes(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<ChangeId, PendingConfigChange<Self::Api>>> {
+        let mut result = MultiValueEncoded::new();
+        for change_id in self.pending_change_ids().iter() {
+            let change = self.pending_change(change_id).get();
+            result.push((change_id, change).into());
+        }
+
+        result
+    }
+
+    #[view(getPendingConfigChange)]
+    fn get_pending_config_change(&self, change_id: ChangeId) -> PendingConfigChange<Self::Api> {
+        let mapper = self.pending_change(change_id);
+        require!(!mapper.is_empty(), UNKNOWN_CHANGE_ERR_MSG);
+
+        mapper.get()
+    }
+
+    #[event("proposeConfigChange")]
+    fn propose_config_change_event(
+        &self,
+        #[indexed] change_id: ChangeId,
+        #[indexed] executable_epoch: Epoch,
+        change: &PendingConfigChange<Self::Api>,
+    );
+
+    #[event("executeConfigChange")]
+    fn execute_config_change_event(
+        &self,
+        #[indexed] change_id: ChangeId,
+        change: &PendingConfigChange<Self::Api>,
+    );
+
+    #[event("cancelConfigChange")]
+    fn cancel_config_change_event(&self, #[indexed] change_id: ChangeId);
+
+    #[view(getTimelockEpochs)]
+    #[storage_mapper("timelockEpochs")]
+    fn timelock_epochs(&self) -> SingleValueMapper<Epoch>;
+
+    #[storage_mapper("lastChangeId")]
+    fn last_change_id(&self) -> SingleValueMapper<ChangeId>;
+
+    #[storage_mapper("pendingChangeIds")]
+    fn pending_change_ids(&self) -> UnorderedSetMapper<ChangeId>;
+
+    #[storage_mapper("pendingChangeId")]
+    fn pending_change_id(
+        &self,
+        function_name: &ManagedBuffer,
+        args: &ManagedVec<ManagedBuffer>,
+    ) -> SingleValueMapper<ChangeId>;
+
+    #[storage_mapper("pendingChange")]
+    fn pending_change(
+        &self,
+        change_id: ChangeId,
+    ) -> SingleValueMapper<PendingConfigChange<Self::Api>>;
+}
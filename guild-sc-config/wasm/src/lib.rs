@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           32
+// Endpoints:                           75
 // Async Callback (empty):               1
-// Total number of exported functions:  35
+// Total number of exported functions:  78
 
 #![no_std]
 
@@ -27,24 +27,49 @@ multiversx_sc_wasm_adapter::endpoints! {
         addUserTiers => add_user_tiers
         updateUserTiers => update_user_tiers
         setUserTierApr => set_user_tier_apr
+        setGuildMasterTiersCurveMode => set_guild_master_tiers_curve_mode
+        setUserTiersCurveMode => set_user_tiers_curve_mode
         getGuildMasterTiers => guild_master_tiers
         getUserTiers => user_tiers
+        getGuildMasterTiersCurveMode => guild_master_tiers_curve_mode
+        getUserTiersCurveMode => user_tiers_curve_mode
         setMinStakeUser => set_min_stake_user
         setMinStakeGuildMaster => set_min_stake_guild_master
         setTotalStakingTokenMinted => set_total_staking_token_minted
+        setGlobalStakingCap => set_global_staking_cap
         increaseStakedTokens => increase_staked_tokens
         decreaseStakedTokens => decrease_staked_tokens
+        getRemainingGlobalStakingCapacity => get_remaining_global_staking_capacity
+        getStakedPercent => get_staked_percent
+        getAverageStakedPercent => get_average_staked_percent
         setSecondsPerBlock => set_seconds_per_block
         setPerBlockRewardAmount => set_per_block_reward_amount
         pauseAllGuilds => pause_all_guilds
         unpauseAllGuilds => unpause_all_guilds
+        setMinUnbondEpochsUser => set_min_unbond_epochs_user
+        setMinUnbondEpochsGuildMaster => set_min_unbond_epochs_guild_master
+        setUnbondEpochsBounds => set_unbond_epochs_bounds
+        getUnbondEpochsBounds => get_unbond_epochs_bounds
+        setClosedGuildExitMode => set_closed_guild_exit_mode
+        setGovernanceSettings => set_governance_settings
+        getGovernanceSettings => get_governance_settings
+        setProtocolFee => set_protocol_fee
+        setCollateralSettings => set_collateral_settings
+        getCollateralSettings => get_collateral_settings
+        setSwapPair => set_swap_pair
+        removeSwapPair => remove_swap_pair
+        getSwapPair => get_swap_pair
         getMaxStakedTokens => max_staked_tokens
         getMinUnbondEpochsUser => min_unbond_epochs_user
         getMinUnbondEpochsGuildMaster => min_unbond_epochs_guild_master
+        getClosedGuildExitMode => closed_guild_exit_mode
+        getProtocolFee => protocol_fee
         getMinStakeUser => min_stake_user
         getMinStakeGuildMaster => min_stake_guild_master
         getTotalStakingTokenMinted => total_staking_token_minted
         getTotalStakingTokenStaked => total_staking_token_staked
+        getStakedPercentAverage => staked_percent_average
+        getGlobalStakingCap => global_staking_cap
         getBaseFarmTokenId => base_farm_token_id
         getBaseUnbondTokenId => base_unbond_token_id
         getBaseTokenDisplayName => base_token_display_name
@@ -52,6 +77,24 @@ multiversx_sc_wasm_adapter::endpoints! {
         getSecondsPerBlock => seconds_per_block
         getPerBlockRewardAmount => per_block_reward_amount
         areAllGuildsPaused => global_pause_status
+        proposeConfigChange => propose_config_change
+        cancelConfigChange => cancel_config_change
+        setTimelockEpochs => set_timelock_epochs
+        getPendingConfigChanges => get_pending_config_changes
+        getPendingConfigChange => get_pending_config_change
+        getTimelockEpochs => timelock_epochs
+        grantRole => grant_role
+        revokeRole => revoke_role
+        hasRole => has_role
+        getRoleMembers => role_members
+        getConfigChangelog => get_config_changelog
+        getLastChangelogEntryId => last_changelog_entry_id
+        setBoostedYieldsFactors => set_boosted_yields_factors
+        setBoostedYieldsRewardsPercentage => set_boosted_yields_rewards_percentage
+        setEnergyFactoryAddress => set_energy_factory_address
+        getBoostedYieldsFactors => get_boosted_yields_factors
+        getEnergyFactoryAddress => get_energy_factory_address
+        getBoostedYieldsRewardsPercentage => boosted_yields_rewards_percentage
     )
 }
 
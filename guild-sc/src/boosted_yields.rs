@@ -0,0 +1,384 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use common_structs::Epoch;
+use energy_factory::energy::Energy;
+use guild_sc_config::boosted_yields::BoostedYieldsFactors;
+
+use crate::custom_rewards::MAX_PERCENT;
+
+pub type Week = usize;
+
+pub const EPOCHS_IN_WEEK: Epoch = 7;
+pub const USER_MAX_CLAIM_WEEKS: Week = 4;
+
+/// Energy and farm amount of the user, counted in the totals of each week starting with `week`
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Default)]
+pub struct BoostedYieldsProgress<M: ManagedTypeApi> {
+    pub week: Week,
+    pub energy: BigUint<M>,
+    pub farm_amount: BigUint<M>,
+}
+
+#[multiversx_sc::module]
+pub trait BoostedYieldsModule:
+    crate::rewards::RewardsModule
+    + crate::config::ConfigModule
+    + permissions_module::PermissionsModule
+    + crate::tiered_rewards::read_config::ReadConfigModule
+    + crate::tiered_rewards::total_tokens::TokenPerTierModule
+    + crate::tokens::farm_token::FarmTokenModule
+    + crate::tokens::request_id::RequestIdModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::user_actions::close_guild::CloseGuildModule
+{
+    #[endpoint(claimBoostedRewards)]
+    fn claim_boosted_rewards(&self) -> EsdtTokenPayment {
+        self.require_not_closing();
+        self.require_not_globally_paused();
+
+        let caller = self.blockchain().get_caller();
+        let boosted_rewards = self
+            .reward_reserve()
+            .update(|reward_reserve| self.claim_boosted_yields_rewards(&caller, reward_reserve));
+
+        let reward_token_id = self.reward_token_id().get();
+        let payment = EsdtTokenPayment::new(reward_token_id, 0, boosted_rewards);
+        if payment.amount > 0 {
+            self.send()
+                .direct_esdt(&caller, &payment.token_identifier, 0, &payment.amount);
+        }
+
+        payment
+    }
+
+    /// Users' energy is only read when they interact with the guild.
+    /// Anyone may update it afterwards, e.g. after the user's energy decreased.
+    #[endpoint(updateEnergyForUser)]
+    fn update_energy_for_user(&self, user: ManagedAddress) {
+        self.require_not_globally_paused();
+
+        self.update_boosted_yields_progress(&user);
+    }
+
+    /// Moves the boosted yields share of the user rewards to the current week's rewards.
+    /// Returns the rewards left for the users.
+    fn split_boosted_yields_rewards(&self, user_rewards: BigUint) -> BigUint {
+        let percentage = self.get_boosted_yields_rewards_percentage();
+        if percentage == 0 || user_rewards == 0 {
+            return user_rewards;
+        }
+
+        let factors = self.get_boosted_yields_factors();
+        if factors.user_rewards_energy_const == 0 && factors.user_rewards_farm_const == 0 {
+            return user_rewards;
+        }
+
+        let boosted_rewards = &user_rewards * percentage / MAX_PERCENT;
+        if boosted_rewards == 0 {
+            return user_rewards;
+        }
+
+        let current_week = self.get_current_week();
+        self.accumulated_rewards_for_week(current_week)
+            .update(|rewards| *rewards += &boosted_rewards);
+
+        user_rewards - boosted_rewards
+    }
+
+    /// Updates the progress of the user, then removes all their unclaimed boosted rewards from the reserve.
+    /// Rewards stay unclaimed if the reserve does not cover them.
+    fn claim_boosted_yields_rewards(
+        &self,
+        user: &ManagedAddress,
+        reward_reserve: &mut BigUint,
+    ) -> BigUint {
+        self.update_boosted_yields_progress(user);
+
+        let unclaimed_mapper = self.unclaimed_boosted_rewards(user);
+        let boosted_rewards = unclaimed_mapper.get();
+        if boosted_rewards == 0 || &boosted_rewards > reward_reserve {
+            return BigUint::zero();
+        }
+
+        unclaimed_mapper.clear();
+        *reward_reserve -= &boosted_rewards;
+
+        boosted_rewards
+    }
+
+    /// Moves the boosted rewards of the completed weeks to the user's unclaimed rewards,
+    /// then updates the user's energy and farm amount in the current week's totals.
+    /// Must be called after each change of the user's staked tokens.
+    fn update_boosted_yields_progress(&self, user: &ManagedAddress) {
+        let guild_master = self.guild_master_address().get();
+        if user == &guild_master {
+            return;
+        }
+
+        let current_week = self.advance_boosted_yields_week();
+        let progress_mapper = self.user_boosted_yields_progress(user);
+        let old_progress = if !progress_mapper.is_empty() {
+            progress_mapper.get()
+        } else {
+            BoostedYieldsProgress {
+                week: current_week,
+                ..Default::default()
+            }
+        };
+
+        let rewards = self.take_boosted_rewards(&old_progress, current_week);
+        if rewards > 0 {
+            self.unclaimed_boosted_rewards(user)
+                .update(|unclaimed| *unclaimed += rewards);
+        }
+
+        // officers are rewarded at the guild master's tier, without boosted yields
+        let farm_amount = if !self.officers().contains(user) {
+            self.user_tokens(user).get()
+        } else {
+            BigUint::zero()
+        };
+        let energy = if farm_amount > 0 {
+            self.get_user_energy(user)
+        } else {
+            BigUint::zero()
+        };
+
+        self.total_energy_for_week(current_week).update(|total| {
+            *total -= &old_progress.energy;
+            *total += &energy;
+        });
+        self.total_farm_amount_for_week(current_week)
+            .update(|total| {
+                *total -= &old_progress.farm_amount;
+                *total += &farm_amount;
+            });
+
+        if farm_amount == 0 {
+            progress_mapper.clear();
+
+            return;
+        }
+
+        progress_mapper.set(BoostedYieldsProgress {
+            week: current_week,
+            energy,
+            farm_amount,
+        });
+    }
+
+    fn calculate_boosted_rewards(
+        &self,
+        progress: &BoostedYieldsProgress<Self::Api>,
+        current_week: Week,
+    ) -> BigUint {
+        let factors = self.get_boosted_yields_factors();
+        let mut total_rewards = BigUint::zero();
+        for week in self.get_claimable_weeks(progress, current_week, &factors) {
+            total_rewards += self.calculate_boosted_rewards_for_week(week, progress, &factors);
+        }
+
+        total_rewards
+    }
+
+    /// Records the rewards taken out of each week, so the rest can be recycled once the week expires
+    fn take_boosted_rewards(
+        &self,
+        progress: &BoostedYieldsProgress<Self::Api>,
+        current_week: Week,
+    ) -> BigUint {
+        let factors = self.get_boosted_yields_factors();
+        let mut total_rewards = BigUint::zero();
+        for week in self.get_claimable_weeks(progress, current_week, &factors) {
+            let rewards = self.calculate_boosted_rewards_for_week(week, progress, &factors);
+            if rewards == 0 {
+                continue;
+            }
+
+            self.claimed_rewards_for_week(week)
+                .update(|claimed| *claimed += &rewards);
+            total_rewards += rewards;
+        }
+
+        total_rewards
+    }
+
+    /// Only the last USER_MAX_CLAIM_WEEKS completed weeks may be claimed
+    fn get_claimable_weeks(
+        &self,
+        progress: &BoostedYieldsProgress<Self::Api>,
+        current_week: Week,
+        factors: &BoostedYieldsFactors<Self::Api>,
+    ) -> core::ops::Range<Week> {
+        if progress.week >= current_week
+            || progress.energy < factors.min_energy_amount
+            || progress.farm_amount < factors.min_farm_amount
+        {
+            return current_week..current_week;
+        }
+
+        let first_week = core::cmp::max(
+            progress.week,
+            current_week.saturating_sub(USER_MAX_CLAIM_WEEKS),
+        );
+
+        first_week..current_week
+    }
+
+    fn calculate_boosted_rewards_for_week(
+        &self,
+        week: Week,
+        progress: &BoostedYieldsProgress<Self::Api>,
+        factors: &BoostedYieldsFactors<Self::Api>,
+    ) -> BigUint {
+        let week_rewards = self.accumulated_rewards_for_week(week).get();
+        let total_farm_amount = self.total_farm_amount_for_week(week).get();
+        let total_consts = &factors.user_rewards_energy_const + &factors.user_rewards_farm_const;
+        if week_rewards == 0 || total_farm_amount == 0 || total_consts == 0 {
+            return BigUint::zero();
+        }
+
+        let max_rewards = &factors.max_rewards_factor * &week_rewards * &progress.farm_amount
+            / &total_farm_amount;
+
+        let total_energy = self.total_energy_for_week(week).get();
+        let rewards_for_energy = if total_energy > 0 {
+            &factors.user_rewards_energy_const * &week_rewards * &progress.energy / total_energy
+        } else {
+            BigUint::zero()
+        };
+        let rewards_for_farm =
+            &factors.user_rewards_farm_const * &week_rewards * &progress.farm_amount
+                / total_farm_amount;
+        let boosted_rewards = (rewards_for_energy + rewards_for_farm) / total_consts;
+
+        core::cmp::min(max_rewards, boosted_rewards)
+    }
+
+    /// Carries the totals of the last updated week over to the current week,
+    /// and recycles the rewards of the weeks that may no longer be claimed
+    fn advance_boosted_yields_week(&self) -> Week {
+        let current_week = self.get_current_week();
+        let last_week_mapper = self.last_boosted_yields_update_week();
+        let last_week = last_week_mapper.get();
+        if last_week >= current_week {
+            return current_week;
+        }
+
+        self.recycle_expired_boosted_rewards(last_week, current_week);
+
+        if last_week > 0 {
+            let total_energy = self.total_energy_for_week(last_week).get();
+            let total_farm_amount = self.total_farm_amount_for_week(last_week).get();
+            for week in last_week + 1..=current_week {
+                self.total_energy_for_week(week).set(&total_energy);
+                self.total_farm_amount_for_week(week)
+                    .set(&total_farm_amount);
+            }
+        }
+
+        last_week_mapper.set(current_week);
+
+        current_week
+    }
+
+    /// Rewards left unclaimed in the weeks that expired since the last update are added to the current week
+    fn recycle_expired_boosted_rewards(&self, last_week: Week, current_week: Week) {
+        let first_expired_week = last_week.saturating_sub(USER_MAX_CLAIM_WEEKS);
+        let last_expired_week = current_week.saturating_sub(USER_MAX_CLAIM_WEEKS);
+        let mut recycled_rewards = BigUint::zero();
+        for week in first_expired_week..last_expired_week {
+            let week_rewards = self.accumulated_rewards_for_week(week).take();
+            let claimed_rewards = self.claimed_rewards_for_week(week).take();
+            if week_rewards > claimed_rewards {
+                recycled_rewards += week_rewards - claimed_rewards;
+            }
+        }
+
+        if recycled_rewards > 0 {
+            self.accumulated_rewards_for_week(current_week)
+                .update(|rewards| *rewards += recycled_rewards);
+        }
+    }
+
+    fn get_user_energy(&self, user: &ManagedAddress) -> BigUint {
+        let energy_factory_address = match self.get_energy_factory_address() {
+            Some(address) => address,
+            None => return BigUint::zero(),
+        };
+
+        let energy_mapper = self.external_user_energy(energy_factory_address, user);
+        if energy_mapper.is_empty() {
+            return BigUint::zero();
+        }
+
+        let mut energy = energy_mapper.get();
+        energy.deplete(self.blockchain().get_block_epoch());
+
+        energy.get_energy_amount()
+    }
+
+    #[view(getCurrentWeek)]
+    fn get_current_week(&self) -> Week {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let first_week_start_epoch = self.first_week_start_epoch().get();
+        if current_epoch < first_week_start_epoch {
+            return 1;
+        }
+
+        ((current_epoch - first_week_start_epoch) / EPOCHS_IN_WEEK) as Week + 1
+    }
+
+    #[view(getUnclaimedBoostedRewards)]
+    fn get_unclaimed_boosted_rewards(&self, user: ManagedAddress) -> BigUint {
+        let progress_mapper = self.user_boosted_yields_progress(&user);
+        let pending_rewards = if !progress_mapper.is_empty() {
+            self.calculate_boosted_rewards(&progress_mapper.get(), self.get_current_week())
+        } else {
+            BigUint::zero()
+        };
+
+        self.unclaimed_boosted_rewards(&user).get() + pending_rewards
+    }
+
+    #[view(getFirstWeekStartEpoch)]
+    #[storage_mapper("firstWeekStartEpoch")]
+    fn first_week_start_epoch(&self) -> SingleValueMapper<Epoch>;
+
+    #[view(getAccumulatedRewardsForWeek)]
+    #[storage_mapper("accumulatedRewardsForWeek")]
+    fn accumulated_rewards_for_week(&self, week: Week) -> SingleValueMapper<BigUint>;
+
+    #[view(getClaimedRewardsForWeek)]
+    #[storage_mapper("claimedRewardsForWeek")]
+    fn claimed_rewards_for_week(&self, week: Week) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalEnergyForWeek)]
+    #[storage_mapper("totalEnergyForWeek")]
+    fn total_energy_for_week(&self, week: Week) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalFarmAmountForWeek)]
+    #[storage_mapper("totalFarmAmountForWeek")]
+    fn total_farm_amount_for_week(&self, week: Week) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("lastBoostedYieldsUpdateWeek")]
+    fn last_boosted_yields_update_week(&self) -> SingleValueMapper<Week>;
+
+    #[view(getUserBoostedYieldsProgress)]
+    #[storage_mapper("userBoostedYieldsProgress")]
+    fn user_boosted_yields_progress(
+        &self,
+        user: &ManagedAddress,
+    ) -> SingleValueMapper<BoostedYieldsProgress<Self::Api>>;
+
+    #[storage_mapper("unclaimedBoostedRewards")]
+    fn unclaimed_boosted_rewards(&self, user: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper_from_address("userEnergy")]
+    fn external_user_energy(
+        &self,
+        sc_addr: ManagedAddress,
+        user: &ManagedAddress,
+    ) -> SingleValueMapper<Energy<Self::Api>, ManagedAddress>;
+}
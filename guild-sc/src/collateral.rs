@@ -0,0 +1,126 @@
+multiversx_sc::imports!();
+
+use common_structs::{Epoch, Percent};
+
+use crate::contexts::storage_cache::StorageCache;
+use crate::custom_rewards::MAX_PERCENT;
+
+mod guild_factory_proxy {
+    multiversx_sc::imports!();
+
+    #[multiversx_sc::proxy]
+    pub trait GuildFactoryProxy {
+        #[endpoint(deactivateGuild)]
+        fn deactivate_guild(&self);
+    }
+}
+
+#[multiversx_sc::module]
+pub trait CollateralModule:
+    crate::rewards::RewardsModule
+    + crate::config::ConfigModule
+    + crate::tokens::farm_token::FarmTokenModule
+    + crate::tokens::request_id::RequestIdModule
+    + permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::tiered_rewards::read_config::ReadConfigModule
+    + crate::tiered_rewards::total_tokens::TokenPerTierModule
+    + crate::user_actions::close_guild::CloseGuildModule
+{
+    /// Starts the grace period once the guild master is undercollateralized, and ends it once they are not.
+    /// Runs whenever the rewards are updated, while the guild is only deactivated through the keeper endpoint.
+    fn update_undercollateralized_deadline(&self, storage_cache: &StorageCache<Self>) {
+        if !self.produces_per_block_rewards() || self.guild_closing().get() {
+            return;
+        }
+
+        let (collateral_ratio, grace_epochs) = self.get_collateral_settings();
+        if grace_epochs == 0 {
+            return;
+        }
+
+        let deadline_mapper = self.undercollateralized_deadline_epoch();
+        if !self
+            .is_guild_master_undercollateralized(collateral_ratio, &storage_cache.farm_token_supply)
+        {
+            deadline_mapper.clear();
+
+            return;
+        }
+
+        if deadline_mapper.is_empty() {
+            let current_epoch = self.blockchain().get_block_epoch();
+            deadline_mapper.set(current_epoch + grace_epochs);
+        }
+    }
+
+    /// Once the guild master has been undercollateralized for the configured grace epochs,
+    /// the guild stops producing rewards and leaves the factory's active guilds.
+    /// The guild master may resume the guild after topping up their stake.
+    fn check_guild_master_collateral(&self, storage_cache: &StorageCache<Self>) {
+        let deadline_mapper = self.undercollateralized_deadline_epoch();
+        if deadline_mapper.is_empty() || self.blockchain().get_block_epoch() < deadline_mapper.get()
+        {
+            return;
+        }
+
+        self.end_produce_rewards();
+        deadline_mapper.clear();
+
+        let guild_factory = self.blockchain().get_owner_address();
+        let _: IgnoreValue = self
+            .collateral_factory_proxy(guild_factory)
+            .deactivate_guild()
+            .execute_on_dest_context();
+
+        let guild_master_tokens = self.guild_master_tokens().get();
+        self.guild_deactivated_event(&guild_master_tokens, &storage_cache.farm_token_supply);
+    }
+
+    fn require_guild_master_collateral(&self) {
+        let (collateral_ratio, grace_epochs) = self.get_collateral_settings();
+        if grace_epochs == 0 {
+            return;
+        }
+
+        let farm_token_supply = self.farm_token_supply().get();
+        require!(
+            !self.is_guild_master_undercollateralized(collateral_ratio, &farm_token_supply),
+            "Guild master stake too low"
+        );
+
+        self.undercollateralized_deadline_epoch().clear();
+    }
+
+    /// Slashed guild master tokens do not count as collateral
+    fn is_guild_master_undercollateralized(
+        &self,
+        collateral_ratio: Percent,
+        farm_token_supply: &BigUint,
+    ) -> bool {
+        let guild_master_tokens = self.guild_master_tokens().get();
+        if guild_master_tokens < self.get_min_stake_guild_master() {
+            return true;
+        }
+
+        guild_master_tokens * MAX_PERCENT < farm_token_supply * collateral_ratio
+    }
+
+    #[proxy]
+    fn collateral_factory_proxy(
+        &self,
+        sc_address: ManagedAddress,
+    ) -> guild_factory_proxy::Proxy<Self::Api>;
+
+    #[event("guildDeactivatedEvent")]
+    fn guild_deactivated_event(
+        &self,
+        #[indexed] guild_master_tokens: &BigUint,
+        #[indexed] total_tokens: &BigUint,
+    );
+
+    /// Empty while the guild master is collateralized
+    #[view(getUndercollateralizedDeadlineEpoch)]
+    #[storage_mapper("undercollateralizedDeadlineEpoch")]
+    fn undercollateralized_deadline_epoch(&self) -> SingleValueMapper<Epoch>;
+}
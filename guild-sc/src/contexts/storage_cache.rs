@@ -24,6 +24,7 @@ pub struct StorageCache<'a, C: FarmContracTraitBounds> {
     pub reward_reserve: BigUint<C::Api>,
     pub user_rps: BigUint<C::Api>,
     pub guild_master_rps: BigUint<C::Api>,
+    pub officer_rps: BigUint<C::Api>,
     pub division_safety_constant: BigUint<C::Api>,
 }
 
@@ -37,6 +38,7 @@ impl<'a, C: FarmContracTraitBounds> StorageCache<'a, C> {
             reward_reserve: sc_ref.reward_reserve().get(),
             user_rps: sc_ref.user_rps().get(),
             guild_master_rps: sc_ref.guild_master_rps().get(),
+            officer_rps: sc_ref.officer_rps().get(),
             division_safety_constant: sc_ref.division_safety_constant().get(),
             sc_ref,
         }
@@ -49,6 +51,7 @@ impl<'a, C: FarmContracTraitBounds> Drop for StorageCache<'a, C> {
         self.sc_ref.reward_reserve().set(&self.reward_reserve);
         self.sc_ref.user_rps().set(&self.user_rps);
         self.sc_ref.guild_master_rps().set(&self.guild_master_rps);
+        self.sc_ref.officer_rps().set(&self.officer_rps);
         self.sc_ref.farm_token_supply().set(&self.farm_token_supply);
     }
 }
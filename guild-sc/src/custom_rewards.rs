@@ -6,7 +6,7 @@ use crate::{
     contexts::storage_cache::StorageCache, farm_base_impl::base_traits_impl::FarmStakingWrapper,
 };
 use common_structs::Percent;
-use guild_sc_config::tier_types::{GuildMasterRewardTier, UserRewardTier};
+use guild_sc_config::tier_types::{GuildMasterRewardTier, TierCurveMode, UserRewardTier};
 
 pub const MAX_PERCENT: Percent = 10_000;
 pub const SECONDS_IN_YEAR: u64 = 31_536_000;
@@ -34,6 +34,9 @@ pub trait CustomRewardsModule:
     + crate::tiered_rewards::read_config::ReadConfigModule
     + crate::tiered_rewards::total_tokens::TokenPerTierModule
     + crate::user_actions::close_guild::CloseGuildModule
+    + crate::boosted_yields::BoostedYieldsModule
+    + crate::sponsor_campaign::SponsorCampaignModule
+    + crate::collateral::CollateralModule
 {
     #[payable("*")]
     #[endpoint(topUpRewards)]
@@ -56,13 +59,54 @@ pub trait CustomRewardsModule:
     #[only_owner]
     #[endpoint(startProduceRewards)]
     fn start_produce_rewards_endpoint(&self) {
+        self.require_guild_master_collateral();
         self.start_produce_rewards();
     }
 
+    /// Rewards produced so far are kept, and no rewards are produced until started again
+    #[only_owner]
+    #[endpoint(stopProduceRewards)]
+    fn stop_produce_rewards_endpoint(&self) {
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        self.end_produce_rewards();
+    }
+
+    /// Anyone may trigger the guild master's collateral check. Returns whether the guild still produces rewards.
+    #[endpoint(checkGuildMasterCollateral)]
+    fn check_guild_master_collateral_endpoint(&self) -> bool {
+        self.require_not_closing();
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+        self.check_guild_master_collateral(&storage_cache);
+
+        self.produces_per_block_rewards()
+    }
+
+    /// Sends the protocol fees accrued since the last withdrawal to the treasury
+    #[only_owner]
+    #[endpoint(withdrawProtocolFees)]
+    fn withdraw_protocol_fees(&self, treasury: ManagedAddress) -> BigUint {
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        let accrued_fees = self.accrued_protocol_fees().take();
+        if accrued_fees > 0 {
+            self.send()
+                .direct_esdt(&treasury, &storage_cache.reward_token_id, 0, &accrued_fees);
+        }
+
+        accrued_fees
+    }
+
     fn get_amount_apr_bounded(&self) -> TotalRewards<Self::Api> {
         let mut total_guild_master = BigUint::zero();
+        let mut total_officers = BigUint::zero();
         let mut total_users = BigUint::zero();
 
+        let officer_tokens = self.officer_tokens().get();
         let guild_master_tokens_total = if !self.guild_master_tokens().is_empty() {
             let total_user_base_tokens = self.total_base_staked_tokens().get();
             let guild_master_apr = self.find_guild_master_tier_apr(&total_user_base_tokens);
@@ -71,37 +115,48 @@ pub trait CustomRewardsModule:
                 self.bound_amount_by_apr(&guild_master_tokens, guild_master_apr);
             total_guild_master += amount_bounded_guild_master;
 
-            guild_master_tokens
+            // officers share the guild master's tier
+            total_officers += self.bound_amount_by_apr(&officer_tokens, guild_master_apr);
+
+            self.get_guild_master_farm_tokens()
         } else {
             BigUint::zero()
         };
 
-        let total_user_tokens = self.farm_token_supply().get() - guild_master_tokens_total;
-        let staked_percent = self.get_total_staked_percent();
+        let total_user_tokens =
+            self.farm_token_supply().get() - guild_master_tokens_total - officer_tokens;
+        let staked_percent = self.get_average_staked_percent();
         let user_apr = self.find_user_tier_apr(staked_percent);
         let amount_bounded = self.bound_amount_by_apr(&total_user_tokens, user_apr);
         total_users += amount_bounded;
 
         TotalRewards {
             guild_master: total_guild_master,
+            officers: total_officers,
             users: total_users,
+            protocol_fee: BigUint::zero(),
         }
     }
 
     // percentage_staked unused
     fn find_guild_master_tier_apr(&self, total_farming_tokens: &BigUint) -> Percent {
         let mapper = self.internal_guild_master_tiers();
-        let tier = self.find_tier_common(total_farming_tokens, Percent::default(), &mapper);
-
-        tier.apr
+        let curve_mode = self.internal_guild_master_tiers_curve_mode().get();
+
+        self.find_apr_common(
+            total_farming_tokens,
+            Percent::default(),
+            &mapper,
+            curve_mode,
+        )
     }
 
     // total_farming_tokens unused
     fn find_user_tier_apr(&self, percentage_staked: Percent) -> Percent {
         let mapper = self.internal_user_tiers();
-        let tier = self.find_tier_common(&BigUint::default(), percentage_staked, &mapper);
+        let curve_mode = self.internal_user_tiers_curve_mode().get();
 
-        tier.apr
+        self.find_apr_common(&BigUint::default(), percentage_staked, &mapper, curve_mode)
     }
 
     fn bound_amount_by_apr(&self, amount: &BigUint, apr: Percent) -> BigUint {
@@ -121,6 +176,24 @@ pub trait CustomRewardsModule:
         unsafe { opt_result.unwrap_unchecked() }
     }
 
+    /// The user tier is found by the time-weighted staked percent, so briefly staking large amounts
+    /// does not reach a higher tier
+    fn get_average_staked_percent(&self) -> u64 {
+        let average_mapper = self.get_staked_percent_average_mapper();
+        if average_mapper.is_empty() {
+            return self.get_total_staked_percent();
+        }
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        average_mapper.get().current_average(current_timestamp)
+    }
+
+    fn accrue_protocol_fee(&self, fee: &BigUint) {
+        self.accrued_protocol_fees()
+            .update(|accrued| *accrued += fee);
+        self.total_protocol_fees().update(|total| *total += fee);
+    }
+
     fn request_rewards(&self, base_amount: BigUint, is_query: bool) -> BigUint {
         let guild_factory = self.blockchain().get_owner_address();
         let received_rewards = self
@@ -159,6 +232,14 @@ pub trait CustomRewardsModule:
         for tier in external_user_tiers_mapper.iter() {
             internal_user_tiers_mapper.push(&tier);
         }
+
+        let guild_master_tiers_curve_mode = self.get_guild_master_tiers_curve_mode();
+        self.internal_guild_master_tiers_curve_mode()
+            .set(guild_master_tiers_curve_mode);
+
+        let user_tiers_curve_mode = self.get_user_tiers_curve_mode();
+        self.internal_user_tiers_curve_mode()
+            .set(user_tiers_curve_mode);
     }
 
     fn update_internal_staking_token_minted(&self) {
@@ -187,6 +268,14 @@ pub trait CustomRewardsModule:
     #[storage_mapper("reward_capacity")]
     fn reward_capacity(&self) -> SingleValueMapper<BigUint>;
 
+    #[view(getAccruedProtocolFees)]
+    #[storage_mapper("accruedProtocolFees")]
+    fn accrued_protocol_fees(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalProtocolFees)]
+    #[storage_mapper("totalProtocolFees")]
+    fn total_protocol_fees(&self) -> SingleValueMapper<BigUint>;
+
     #[storage_mapper("internalSecondsPerBlock")]
     fn internal_seconds_per_block(&self) -> SingleValueMapper<u64>;
 
@@ -196,6 +285,12 @@ pub trait CustomRewardsModule:
     #[storage_mapper("internalUserTiers")]
     fn internal_user_tiers(&self) -> VecMapper<UserRewardTier>;
 
+    #[storage_mapper("internalGuildMasterTiersCurveMode")]
+    fn internal_guild_master_tiers_curve_mode(&self) -> SingleValueMapper<TierCurveMode>;
+
+    #[storage_mapper("internalUserTiersCurveMode")]
+    fn internal_user_tiers_curve_mode(&self) -> SingleValueMapper<TierCurveMode>;
+
     #[storage_mapper("internalTotalStakingTokenMinted")]
     fn internal_total_staking_token_minted(&self) -> SingleValueMapper<BigUint>;
 }
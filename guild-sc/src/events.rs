@@ -25,6 +25,7 @@ pub struct ExitFarmEvent<M: ManagedTypeApi> {
     farming_token_amount: BigUint<M>,
     farm_supply: BigUint<M>,
     reward_tokens: EsdtTokenPayment<M>,
+    rewards_receiver: ManagedAddress<M>,
     reward_reserve: BigUint<M>,
     farm_attributes: ManagedBuffer<M>,
 }
@@ -35,6 +36,7 @@ pub struct ClaimRewardsEvent<M: ManagedTypeApi> {
     new_farm_token: EsdtTokenPayment<M>,
     farm_supply: BigUint<M>,
     reward_tokens: EsdtTokenPayment<M>,
+    rewards_receiver: ManagedAddress<M>,
     reward_reserve: BigUint<M>,
     old_farm_attributes: ManagedBuffer<M>,
     new_farm_attributes: ManagedBuffer<M>,
@@ -105,6 +107,7 @@ pub trait EventsModule {
         original_attributes: AttributesType,
         output_farming_tokens: EsdtTokenPayment<Self::Api>,
         output_reward: EsdtTokenPayment<Self::Api>,
+        rewards_receiver: &ManagedAddress,
         storage_cache: StorageCache<'a, C>,
     ) {
         let epoch = self.blockchain().get_block_epoch();
@@ -125,6 +128,7 @@ pub trait EventsModule {
                 farming_token_amount: output_farming_tokens.amount,
                 farm_supply: storage_cache.farm_token_supply.clone(),
                 reward_tokens: output_reward,
+                rewards_receiver: rewards_receiver.clone(),
                 reward_reserve: storage_cache.reward_reserve.clone(),
                 farm_attributes,
             },
@@ -141,6 +145,7 @@ pub trait EventsModule {
         claim_rewards_context: ClaimRewardsContext<Self::Api, AttributesType>,
         output_farm_token: PaymentAttributesPair<Self::Api, AttributesType>,
         output_reward: EsdtTokenPayment<Self::Api>,
+        rewards_receiver: &ManagedAddress,
         created_with_merge: bool,
         storage_cache: StorageCache<'a, C>,
     ) {
@@ -170,6 +175,7 @@ pub trait EventsModule {
                 new_farm_token: output_farm_token.payment,
                 farm_supply: storage_cache.farm_token_supply.clone(),
                 reward_tokens: output_reward,
+                rewards_receiver: rewards_receiver.clone(),
                 reward_reserve: storage_cache.reward_reserve.clone(),
                 old_farm_attributes,
                 new_farm_attributes,
@@ -4,13 +4,13 @@ use core::marker::PhantomData;
 
 use crate::config::ConfigModule;
 use crate::contexts::storage_cache::StorageCache;
-use crate::custom_rewards::CustomRewardsModule;
+use crate::custom_rewards::{CustomRewardsModule, MAX_PERCENT};
 use crate::rewards::RewardsModule;
 use crate::tiered_rewards::total_tokens::TokenPerTierModule;
 use crate::tokens::farm_token::FarmTokenModule;
 use crate::tokens::token_attributes::{LocalFarmToken, StakingFarmTokenAttributes};
 use crate::user_actions::close_guild::CloseGuildModule;
-use common_structs::Nonce;
+use common_structs::{Nonce, Percent};
 
 pub trait FarmStakingTraits:
     crate::custom_rewards::CustomRewardsModule
@@ -22,6 +22,9 @@ pub trait FarmStakingTraits:
     + crate::tiered_rewards::read_config::ReadConfigModule
     + crate::tiered_rewards::total_tokens::TokenPerTierModule
     + crate::user_actions::close_guild::CloseGuildModule
+    + crate::boosted_yields::BoostedYieldsModule
+    + crate::sponsor_campaign::SponsorCampaignModule
+    + crate::collateral::CollateralModule
 {
 }
 
@@ -35,24 +38,40 @@ impl<T> FarmStakingTraits for T where
         + crate::tiered_rewards::read_config::ReadConfigModule
         + crate::tiered_rewards::total_tokens::TokenPerTierModule
         + crate::user_actions::close_guild::CloseGuildModule
+        + crate::boosted_yields::BoostedYieldsModule
+        + crate::sponsor_campaign::SponsorCampaignModule
+        + crate::collateral::CollateralModule
 {
 }
 
 pub struct TotalRewards<M: ManagedTypeApi> {
     pub guild_master: BigUint<M>,
+    pub officers: BigUint<M>,
     pub users: BigUint<M>,
+    pub protocol_fee: BigUint<M>,
 }
 
 impl<M: ManagedTypeApi> TotalRewards<M> {
     pub fn zero() -> Self {
         Self {
             guild_master: BigUint::zero(),
+            officers: BigUint::zero(),
             users: BigUint::zero(),
+            protocol_fee: BigUint::zero(),
         }
     }
 
     pub fn total(&self) -> BigUint<M> {
-        &self.guild_master + &self.users
+        &self.guild_master + &self.officers + &self.users + &self.protocol_fee
+    }
+
+    /// Moves the protocol fee share of each part to `protocol_fee`
+    pub fn apply_protocol_fee(&mut self, fee: Percent) {
+        for part in [&mut self.guild_master, &mut self.officers, &mut self.users] {
+            let part_fee = &*part * fee / MAX_PERCENT;
+            *part -= &part_fee;
+            self.protocol_fee += part_fee;
+        }
     }
 }
 
@@ -98,41 +117,63 @@ pub trait FarmContract {
         let guild_master_tokens = sc.guild_master_tokens().get();
         let total_user_tokens = sc.farm_token_supply().get();
         let guild_master_rewards =
-            &guild_master_tokens * &extra_rewards_unbounded / total_user_tokens;
-        let user_rewards = &extra_rewards_unbounded - &guild_master_rewards;
+            &guild_master_tokens * &extra_rewards_unbounded / &total_user_tokens;
+        let officer_tokens = sc.officer_tokens().get();
+        let officer_rewards = &officer_tokens * &extra_rewards_unbounded / &total_user_tokens;
+        let user_rewards = &extra_rewards_unbounded - &guild_master_rewards - &officer_rewards;
         let extra_rewards_unbounded_split = TotalRewards {
             guild_master: guild_master_rewards,
+            officers: officer_rewards,
             users: user_rewards,
+            protocol_fee: BigUint::zero(),
         };
 
         let extra_rewards_apr_bounded_per_block = sc.get_amount_apr_bounded();
         let block_nonce_diff = current_block_nonce - last_reward_nonce;
         let extra_rewards_apr_bounded = TotalRewards {
             guild_master: extra_rewards_apr_bounded_per_block.guild_master * block_nonce_diff,
+            officers: extra_rewards_apr_bounded_per_block.officers * block_nonce_diff,
             users: extra_rewards_apr_bounded_per_block.users * block_nonce_diff,
+            protocol_fee: BigUint::zero(),
         };
 
-        TotalRewards {
+        let mut total_rewards = TotalRewards {
             guild_master: core::cmp::min(
                 extra_rewards_unbounded_split.guild_master,
                 extra_rewards_apr_bounded.guild_master,
             ),
+            officers: core::cmp::min(
+                extra_rewards_unbounded_split.officers,
+                extra_rewards_apr_bounded.officers,
+            ),
             users: core::cmp::min(
                 extra_rewards_unbounded_split.users,
                 extra_rewards_apr_bounded.users,
             ),
+            protocol_fee: BigUint::zero(),
+        };
+
+        let protocol_fee = sc.get_protocol_fee();
+        if protocol_fee > 0 {
+            total_rewards.apply_protocol_fee(protocol_fee);
         }
+
+        total_rewards
     }
 
     fn generate_aggregated_rewards(
         sc: &Self::FarmSc,
         storage_cache: &mut StorageCache<Self::FarmSc>,
     ) {
+        sc.generate_sponsor_campaign_rewards(storage_cache);
+
         let accumulated_rewards_mapper = sc.accumulated_rewards();
         let mut accumulated_rewards = accumulated_rewards_mapper.get();
         let reward_capacity = sc.reward_capacity().get();
         let mut remaining_rewards = &reward_capacity - &accumulated_rewards;
         let split_rewards = Self::mint_per_block_rewards(sc);
+        sc.update_undercollateralized_deadline(storage_cache);
+
         let total_reward = split_rewards.total();
         if total_reward > remaining_rewards {
             let caller = sc.blockchain().get_caller();
@@ -151,8 +192,11 @@ pub trait FarmContract {
             return;
         }
 
-        storage_cache.reward_reserve += &total_reward;
+        storage_cache.reward_reserve += &total_reward - &split_rewards.protocol_fee;
         accumulated_rewards += &total_reward;
+        if split_rewards.protocol_fee > 0 {
+            sc.accrue_protocol_fee(&split_rewards.protocol_fee);
+        }
         accumulated_rewards_mapper.set(&accumulated_rewards);
 
         if storage_cache.farm_token_supply == 0 {
@@ -161,18 +205,29 @@ pub trait FarmContract {
             return;
         }
 
-        let guild_master_tokens = sc.guild_master_tokens().get();
-        if guild_master_tokens > 0 {
+        // slashed tokens are not rewarded, but are still part of the guild master's farm tokens
+        let guild_master_farm_tokens = sc.get_guild_master_farm_tokens();
+        if guild_master_farm_tokens > 0 {
             let increase_guild_master = (split_rewards.guild_master
                 * &storage_cache.division_safety_constant)
-                / &guild_master_tokens;
+                / &guild_master_farm_tokens;
             storage_cache.guild_master_rps += increase_guild_master;
         }
 
-        let total_user_tokens = &sc.farm_token_supply().get() - &guild_master_tokens;
+        let officer_tokens = sc.officer_tokens().get();
+        if officer_tokens > 0 {
+            let increase_officers = (split_rewards.officers
+                * &storage_cache.division_safety_constant)
+                / &officer_tokens;
+            storage_cache.officer_rps += increase_officers;
+        }
+
+        let total_user_tokens =
+            &sc.farm_token_supply().get() - &guild_master_farm_tokens - &officer_tokens;
         if total_user_tokens > 0 {
-            let increase_users = (split_rewards.users * &storage_cache.division_safety_constant)
-                / &total_user_tokens;
+            let user_rewards = sc.split_boosted_yields_rewards(split_rewards.users);
+            let increase_users =
+                (user_rewards * &storage_cache.division_safety_constant) / &total_user_tokens;
             storage_cache.user_rps += increase_users;
         }
 
@@ -188,7 +243,7 @@ pub trait FarmContract {
     ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
         let storage_rps = sc.get_rps_by_user(caller, storage_cache);
         let token_rps = token_attributes.get_reward_per_share();
-        if storage_rps <= &token_rps {
+        if storage_rps <= token_rps {
             return BigUint::zero();
         }
 
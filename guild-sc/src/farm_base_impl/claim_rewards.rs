@@ -45,6 +45,7 @@ pub trait BaseClaimRewardsModule:
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + super::base_farm_validation::BaseFarmValidationModule
     + utils::UtilsModule
+    + crate::reward_distribution::RewardDistributionModule
 {
     fn claim_rewards_base<FC: FarmContract<FarmSc = Self>>(
         &self,
@@ -84,6 +85,7 @@ pub trait BaseClaimRewardsModule:
             &farm_token_mapper,
         );
         new_token_attributes.set_reward_per_share(rps.clone());
+        self.add_position_weight(&caller, &new_token_attributes.current_farm_amount, &rps);
 
         let first_farm_token = &temp_result.context.first_farm_token.payment;
         farm_token_mapper.nft_burn(first_farm_token.token_nonce, &first_farm_token.amount);
@@ -134,6 +136,8 @@ pub trait BaseClaimRewardsModule:
             &claim_rewards_context,
         );
 
+        let total_rewards = self.settle_distributed_rewards(caller, total_rewards);
+        self.remove_claimed_positions_weight(caller, &claim_rewards_context);
         storage_cache.reward_reserve -= &total_rewards;
 
         TempInternalClaimRewardsResult {
@@ -143,6 +147,30 @@ pub trait BaseClaimRewardsModule:
         }
     }
 
+    fn remove_claimed_positions_weight(
+        &self,
+        caller: &ManagedAddress,
+        claim_rewards_context: &ClaimRewardsContext<
+            Self::Api,
+            StakingFarmTokenAttributes<Self::Api>,
+        >,
+    ) {
+        let first_farm_token = &claim_rewards_context.first_farm_token;
+        self.remove_position_weight(
+            caller,
+            &first_farm_token.payment.amount,
+            &first_farm_token.attributes.reward_per_share,
+        );
+
+        for (payment, attributes) in claim_rewards_context.additional_payments.iter().zip(
+            claim_rewards_context
+                .additional_token_attributes
+                .into_iter(),
+        ) {
+            self.remove_position_weight(caller, &payment.amount, &attributes.reward_per_share);
+        }
+    }
+
     fn get_first_token_part_attributes<FC: FarmContract<FarmSc = Self>>(
         &self,
         claim_rewards_context: &ClaimRewardsContext<
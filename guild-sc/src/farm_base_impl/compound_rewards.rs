@@ -62,6 +62,7 @@ pub trait BaseCompoundRewardsModule:
             &farm_token_mapper,
         );
         new_token_attributes.set_reward_per_share(rps.clone());
+        self.add_position_weight(&caller, &new_token_attributes.current_farm_amount, &rps);
 
         let new_farm_token = farm_token_mapper.nft_create(
             new_token_attributes.get_total_supply(),
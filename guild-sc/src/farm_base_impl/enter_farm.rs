@@ -34,6 +34,7 @@ pub trait BaseEnterFarmModule:
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + super::base_farm_validation::BaseFarmValidationModule
     + utils::UtilsModule
+    + crate::reward_distribution::RewardDistributionModule
 {
     fn enter_farm_base<FC: FarmContract<FarmSc = Self>>(
         &self,
@@ -79,6 +80,14 @@ pub trait BaseEnterFarmModule:
             &enter_farm_context.additional_farm_tokens,
             &farm_token_mapper,
         );
+
+        // merging keeps the total rps weight, so only the new farming tokens are added
+        self.add_position_weight(
+            &caller,
+            &enter_farm_context.farming_token_payment.amount,
+            &rps,
+        );
+
         let new_farm_token = PaymentAttributesPair {
             payment: EsdtTokenPayment::new(
                 storage_cache.farm_token_id.clone(),
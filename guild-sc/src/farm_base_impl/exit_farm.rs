@@ -34,6 +34,7 @@ pub trait BaseExitFarmModule:
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + super::base_farm_validation::BaseFarmValidationModule
     + utils::UtilsModule
+    + crate::reward_distribution::RewardDistributionModule
 {
     fn exit_farm_base<FC: FarmContract<FarmSc = Self>>(
         &self,
@@ -66,6 +67,13 @@ pub trait BaseExitFarmModule:
             &token_attributes,
             &storage_cache,
         );
+
+        let rewards = self.settle_distributed_rewards(&caller, rewards);
+        self.remove_position_weight(
+            &caller,
+            farm_token_amount,
+            &token_attributes.reward_per_share,
+        );
         storage_cache.reward_reserve -= &rewards;
 
         let farming_token_amount = token_attributes.get_total_supply();
@@ -0,0 +1,399 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use common_structs::{Epoch, Percent};
+use permissions_module::Permissions;
+
+use crate::contexts::storage_cache::StorageCache;
+use crate::custom_rewards::MAX_PERCENT;
+use crate::farm_base_impl::base_traits_impl::{FarmContract, FarmStakingWrapper};
+use crate::user_actions::close_guild::MigratedPosition;
+
+pub type ProposalId = u64;
+
+pub const MAX_ACTIVE_PROPOSALS: usize = 10;
+pub const MIN_PROPOSER_STAKE_PERCENT: Percent = 100;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub enum ProposalAction<M: ManagedTypeApi> {
+    ReplaceGuildMaster(ManagedAddress<M>),
+    PauseGuild,
+    UnpauseGuild,
+    SetUserUnbondEpochs(Epoch),
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum ProposalStatus {
+    Active,
+    Executed,
+    Rejected,
+    Cancelled,
+}
+
+/// Passed proposals may be executed from end_epoch until expiry_epoch
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct Proposal<M: ManagedTypeApi> {
+    pub proposer: ManagedAddress<M>,
+    pub action: ProposalAction<M>,
+    pub end_epoch: Epoch,
+    pub expiry_epoch: Epoch,
+    pub quorum_votes: BigUint<M>,
+    pub votes_for: BigUint<M>,
+    pub votes_against: BigUint<M>,
+    pub status: ProposalStatus,
+}
+
+#[multiversx_sc::module]
+pub trait GovernanceModule:
+    crate::custom_rewards::CustomRewardsModule
+    + crate::rewards::RewardsModule
+    + crate::config::ConfigModule
+    + crate::events::EventsModule
+    + token_send::TokenSendModule
+    + crate::tokens::farm_token::FarmTokenModule
+    + crate::tokens::unbond_token::UnbondTokenModule
+    + crate::tokens::request_id::RequestIdModule
+    + utils::UtilsModule
+    + permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::farm_base_impl::base_farm_validation::BaseFarmValidationModule
+    + crate::farm_base_impl::enter_farm::BaseEnterFarmModule
+    + crate::farm_base_impl::exit_farm::BaseExitFarmModule
+    + crate::tiered_rewards::read_config::ReadConfigModule
+    + crate::tiered_rewards::total_tokens::TokenPerTierModule
+    + crate::tiered_rewards::call_config::CallConfigModule
+    + crate::reward_distribution::RewardDistributionModule
+    + crate::boosted_yields::BoostedYieldsModule
+    + crate::user_actions::close_guild::CloseGuildModule
+    + crate::user_actions::unbond_epochs::UnbondEpochsModule
+    + crate::user_actions::custom_events::CustomEventsModule
+    + crate::user_actions::unstake_farm::UnstakeFarmModule
+    + crate::user_actions::unbond_farm::UnbondFarmModule
+    + crate::user_actions::stake_farm::StakeFarmModule
+    + crate::user_actions::migration::MigrationModule
+{
+    /// Members holding at least 1% of the members' stake may each have one active proposal.
+    /// Voting power is the stake of the members when the proposal is created.
+    #[endpoint(createProposal)]
+    fn create_proposal(&self, action: ProposalAction<Self::Api>) -> ProposalId {
+        self.require_not_closing();
+        require!(
+            self.active_proposals().len() < MAX_ACTIVE_PROPOSALS,
+            "Too many active proposals"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let caller_tokens = self.user_tokens(&caller).get();
+        require!(caller_tokens > 0, "Only members may create proposals");
+
+        let total_user_tokens =
+            self.farm_token_supply().get() - self.get_guild_master_farm_tokens();
+        require!(
+            caller_tokens * MAX_PERCENT >= &total_user_tokens * MIN_PROPOSER_STAKE_PERCENT,
+            "Not enough stake to create proposals"
+        );
+        for proposal_id in self.active_proposals().iter() {
+            require!(
+                self.proposal(proposal_id).get().proposer != caller,
+                "Proposer already has an active proposal"
+            );
+        }
+
+        match &action {
+            ProposalAction::ReplaceGuildMaster(new_guild_master) => {
+                let guild_master = self.guild_master_address().get();
+                require!(
+                    !new_guild_master.is_zero() && new_guild_master != &guild_master,
+                    "Invalid guild master"
+                );
+                self.require_no_stake_in_guild(new_guild_master);
+                self.guild_master_candidate_proposals(new_guild_master)
+                    .update(|proposals| *proposals += 1);
+            }
+            ProposalAction::SetUserUnbondEpochs(unbond_epochs) => {
+                let (lower_bound, upper_bound) = self.get_unbond_epochs_bounds();
+                require!(
+                    *unbond_epochs >= lower_bound && *unbond_epochs <= upper_bound,
+                    "Invalid unbond epochs"
+                );
+            }
+            _ => {}
+        }
+
+        let (voting_period_epochs, quorum) = self.get_governance_settings();
+        let current_epoch = self.blockchain().get_block_epoch();
+        let proposal = Proposal {
+            proposer: caller.clone(),
+            action,
+            end_epoch: current_epoch + voting_period_epochs,
+            expiry_epoch: current_epoch + 2 * voting_period_epochs,
+            quorum_votes: total_user_tokens * quorum / MAX_PERCENT,
+            votes_for: BigUint::zero(),
+            votes_against: BigUint::zero(),
+            status: ProposalStatus::Active,
+        };
+
+        let proposal_id = self.last_proposal_id().update(|id| {
+            *id += 1;
+            *id
+        });
+        self.proposal(proposal_id).set(&proposal);
+        let _ = self.active_proposals().insert(proposal_id);
+
+        self.proposal_created_event(&caller, proposal_id, &proposal);
+
+        proposal_id
+    }
+
+    #[endpoint(vote)]
+    fn vote(&self, proposal_id: ProposalId, support: bool) {
+        self.require_not_closing();
+        self.require_active_proposal(proposal_id);
+
+        let caller = self.blockchain().get_caller();
+        let proposal_mapper = self.proposal(proposal_id);
+        let mut proposal = proposal_mapper.get();
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(current_epoch < proposal.end_epoch, "Voting period over");
+        let has_voted_mapper = self.has_voted(proposal_id, &caller);
+        require!(!has_voted_mapper.get(), "Already voted");
+        has_voted_mapper.set(true);
+
+        let voting_power = self.get_voting_power(proposal_id, caller.clone());
+        require!(voting_power > 0, "No voting power");
+
+        if support {
+            proposal.votes_for += &voting_power;
+        } else {
+            proposal.votes_against += &voting_power;
+        }
+        proposal_mapper.set(proposal);
+
+        self.vote_event(&caller, proposal_id, support, &voting_power);
+    }
+
+    /// A proposal passes if it reached the quorum, and has more votes for than against
+    #[endpoint(executeProposal)]
+    fn execute_proposal(&self, proposal_id: ProposalId) -> ProposalStatus {
+        self.require_not_closing();
+        self.require_active_proposal(proposal_id);
+
+        let proposal_mapper = self.proposal(proposal_id);
+        let mut proposal = proposal_mapper.get();
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(
+            current_epoch >= proposal.end_epoch,
+            "Voting period not over"
+        );
+        require!(current_epoch < proposal.expiry_epoch, "Proposal expired");
+
+        let total_votes = &proposal.votes_for + &proposal.votes_against;
+        let passed = total_votes > 0
+            && total_votes >= proposal.quorum_votes
+            && proposal.votes_for > proposal.votes_against;
+
+        self.remove_active_proposal(proposal_id, &proposal);
+        if passed {
+            self.apply_proposal_action(proposal.action.clone());
+            proposal.status = ProposalStatus::Executed;
+        } else {
+            proposal.status = ProposalStatus::Rejected;
+        }
+        proposal_mapper.set(&proposal);
+
+        let caller = self.blockchain().get_caller();
+        self.proposal_finalized_event(&caller, proposal_id, proposal.status);
+
+        proposal.status
+    }
+
+    /// The proposer may cancel the proposal during the voting period,
+    /// while anyone may cancel it once it expired without being executed
+    #[endpoint(cancelProposal)]
+    fn cancel_proposal(&self, proposal_id: ProposalId) {
+        self.require_active_proposal(proposal_id);
+
+        let caller = self.blockchain().get_caller();
+        let proposal_mapper = self.proposal(proposal_id);
+        let mut proposal = proposal_mapper.get();
+        let current_epoch = self.blockchain().get_block_epoch();
+        let proposer_cancel = caller == proposal.proposer && current_epoch < proposal.end_epoch;
+        require!(
+            proposer_cancel || current_epoch >= proposal.expiry_epoch,
+            "May not cancel proposal"
+        );
+
+        self.remove_active_proposal(proposal_id, &proposal);
+        proposal.status = ProposalStatus::Cancelled;
+        proposal_mapper.set(&proposal);
+
+        self.proposal_finalized_event(&caller, proposal_id, proposal.status);
+    }
+
+    fn apply_proposal_action(&self, action: ProposalAction<Self::Api>) {
+        match action {
+            ProposalAction::ReplaceGuildMaster(new_guild_master) => {
+                self.replace_guild_master(new_guild_master)
+            }
+            ProposalAction::PauseGuild => self.guild_paused().set(true),
+            ProposalAction::UnpauseGuild => self.guild_paused().set(false),
+            ProposalAction::SetUserUnbondEpochs(unbond_epochs) => {
+                self.user_unbond_epochs_override().set(unbond_epochs)
+            }
+        }
+    }
+
+    /// The stake of the current guild master is unbonded, and their farm tokens may only be sent to `claimMigratedRewards` afterwards.
+    /// The new guild master must stake before the guild produces rewards again.
+    fn replace_guild_master(&self, new_guild_master: ManagedAddress) {
+        let old_guild_master = self.guild_master_address().get();
+        require!(
+            new_guild_master != old_guild_master,
+            "Already the guild master"
+        );
+        self.require_no_stake_in_guild(&new_guild_master);
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        let rewards = if !self.member_rewards_info(&old_guild_master).is_empty() {
+            let rewards = self.take_undistributed_rewards(
+                &old_guild_master,
+                &storage_cache.guild_master_rps,
+                &storage_cache.division_safety_constant,
+            );
+            self.remove_migrated_member(&old_guild_master);
+
+            rewards
+        } else {
+            BigUint::zero()
+        };
+        storage_cache.reward_reserve -= &rewards;
+        if rewards > 0 {
+            self.send().direct_esdt(
+                &old_guild_master,
+                &storage_cache.reward_token_id,
+                0,
+                &rewards,
+            );
+        }
+
+        storage_cache.farm_token_supply -= self.get_guild_master_farm_tokens();
+        let staked_tokens = self.guild_master_tokens().take();
+        self.slashed_guild_master_tokens().clear();
+        self.migrated_positions(&old_guild_master)
+            .push(&MigratedPosition {
+                last_farm_token_nonce: self.get_last_farm_token_nonce(),
+                reward_per_share: storage_cache.guild_master_rps.clone(),
+            });
+
+        let unbond_token = if staked_tokens > 0 {
+            self.remove_total_base_staked_tokens(&staked_tokens);
+            self.call_decrease_total_staked_tokens(staked_tokens.clone());
+
+            let unbond_epochs = self.get_min_unbond_epochs_guild_master();
+            self.create_and_send_unbond_tokens(
+                &old_guild_master,
+                staked_tokens,
+                None,
+                unbond_epochs,
+            )
+            .unbond_token
+        } else {
+            EsdtTokenPayment::new(self.unbond_token().get_token_id(), 0, BigUint::zero())
+        };
+
+        self.remove_permissions(old_guild_master.clone(), Permissions::ADMIN);
+        self.add_permissions(new_guild_master.clone(), Permissions::ADMIN);
+        self.guild_master_address().set(&new_guild_master);
+
+        // the new guild master has no stake, so no officer tokens are left
+        let _ = self.officers().swap_remove(&new_guild_master);
+
+        let guild_factory = self.blockchain().get_owner_address();
+        let _: IgnoreValue = self
+            .factory_proxy(guild_factory)
+            .transfer_guild_master(new_guild_master.clone())
+            .execute_on_dest_context();
+
+        self.guild_master_replaced_event(&old_guild_master, &new_guild_master, &unbond_token);
+    }
+
+    /// Positions of members are tracked with the member rewards, so they may not become the guild master
+    fn require_no_stake_in_guild(&self, new_guild_master: &ManagedAddress) {
+        require!(
+            self.user_tokens(new_guild_master).get() == 0
+                && self.member_rewards_info(new_guild_master).is_empty(),
+            "New guild master may not have a stake in this guild"
+        );
+    }
+
+    fn remove_active_proposal(&self, proposal_id: ProposalId, proposal: &Proposal<Self::Api>) {
+        let _ = self.active_proposals().swap_remove(&proposal_id);
+        if let ProposalAction::ReplaceGuildMaster(new_guild_master) = &proposal.action {
+            self.guild_master_candidate_proposals(new_guild_master)
+                .update(|proposals| *proposals -= 1);
+        }
+    }
+
+    fn require_active_proposal(&self, proposal_id: ProposalId) {
+        require!(
+            self.active_proposals().contains(&proposal_id),
+            "Proposal not active"
+        );
+    }
+
+    /// The stake of the user when the proposal was created
+    #[view(getVotingPower)]
+    fn get_voting_power(&self, proposal_id: ProposalId, user: ManagedAddress) -> BigUint {
+        match self.user_tokens_snapshot(proposal_id, &user).get() {
+            Some(tokens) => tokens,
+            None => self.user_tokens(&user).get(),
+        }
+    }
+
+    #[view(getLastProposalId)]
+    #[storage_mapper("lastProposalId")]
+    fn last_proposal_id(&self) -> SingleValueMapper<ProposalId>;
+
+    #[view(getProposal)]
+    #[storage_mapper("proposal")]
+    fn proposal(&self, proposal_id: ProposalId) -> SingleValueMapper<Proposal<Self::Api>>;
+
+    #[view(hasVoted)]
+    #[storage_mapper("hasVoted")]
+    fn has_voted(&self, proposal_id: ProposalId, user: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[event("proposalCreatedEvent")]
+    fn proposal_created_event(
+        &self,
+        #[indexed] proposer: &ManagedAddress,
+        #[indexed] proposal_id: ProposalId,
+        proposal: &Proposal<Self::Api>,
+    );
+
+    #[event("voteEvent")]
+    fn vote_event(
+        &self,
+        #[indexed] voter: &ManagedAddress,
+        #[indexed] proposal_id: ProposalId,
+        #[indexed] support: bool,
+        voting_power: &BigUint,
+    );
+
+    #[event("proposalFinalizedEvent")]
+    fn proposal_finalized_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] proposal_id: ProposalId,
+        #[indexed] status: ProposalStatus,
+    );
+
+    #[event("guildMasterReplacedEvent")]
+    fn guild_master_replaced_event(
+        &self,
+        #[indexed] old_guild_master: &ManagedAddress,
+        #[indexed] new_guild_master: &ManagedAddress,
+        unbond_token: &EsdtTokenPayment,
+    );
+}
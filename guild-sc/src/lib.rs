@@ -11,12 +11,19 @@ use fixed_supply_token::FixedSupplyToken;
 use permissions_module::Permissions;
 use tokens::token_attributes::StakingFarmTokenAttributes;
 
+pub mod boosted_yields;
+pub mod collateral;
 pub mod config;
 pub mod contexts;
 pub mod custom_rewards;
 pub mod events;
 pub mod farm_base_impl;
+pub mod governance;
+pub mod officers;
+pub mod reward_distribution;
 pub mod rewards;
+pub mod slashing;
+pub mod sponsor_campaign;
 pub mod tiered_rewards;
 pub mod tokens;
 pub mod user_actions;
@@ -50,6 +57,16 @@ pub trait FarmStaking:
     + user_actions::migration::MigrationModule
     + user_actions::custom_events::CustomEventsModule
     + user_actions::close_guild::CloseGuildModule
+    + user_actions::unbond_epochs::UnbondEpochsModule
+    + reward_distribution::RewardDistributionModule
+    + user_actions::distribute_rewards::DistributeRewardsModule
+    + user_actions::swap::SwapModule
+    + boosted_yields::BoostedYieldsModule
+    + slashing::SlashingModule
+    + governance::GovernanceModule
+    + officers::OfficersModule
+    + sponsor_campaign::SponsorCampaignModule
+    + collateral::CollateralModule
 {
     #[init]
     fn init(
@@ -77,11 +94,17 @@ pub trait FarmStaking:
         self.config_sc_address().set(config_sc_address);
         self.guild_master_address().set(guild_master);
 
+        let current_epoch = self.blockchain().get_block_epoch();
+        self.first_week_start_epoch().set(current_epoch);
+
         self.update_all();
     }
 
     #[upgrade]
-    fn upgrade(&self) {}
+    fn upgrade(&self) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        self.first_week_start_epoch().set_if_empty(current_epoch);
+    }
 
     #[payable("*")]
     #[endpoint(mergeFarmTokens)]
@@ -91,6 +114,7 @@ pub trait FarmStaking:
 
         let caller = self.blockchain().get_caller();
         let payments = self.get_non_empty_payments();
+        self.require_not_migrated(&caller, &payments);
         let token_mapper = self.farm_token();
         token_mapper.require_all_same_token(&payments);
 
@@ -130,13 +154,21 @@ pub trait FarmStaking:
         let mut storage_cache = StorageCache::new(self);
         FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
 
-        FarmStakingWrapper::<Self>::calculate_rewards(
+        let rewards = FarmStakingWrapper::<Self>::calculate_rewards(
             self,
             &user,
             &farm_token_amount,
             &attributes,
             &storage_cache,
-        )
+        );
+
+        // rewards already sent by keepers are deducted when claiming
+        let distributed_rewards = self.get_distributed_rewards(user);
+        if rewards > distributed_rewards {
+            rewards - distributed_rewards
+        } else {
+            BigUint::zero()
+        }
     }
 
     fn base_farm_init(
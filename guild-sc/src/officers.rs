@@ -0,0 +1,100 @@
+multiversx_sc::imports!();
+
+use crate::contexts::storage_cache::StorageCache;
+use crate::farm_base_impl::base_traits_impl::{FarmContract, FarmStakingWrapper};
+
+pub const MAX_OFFICERS: usize = 5;
+
+#[multiversx_sc::module]
+pub trait OfficersModule:
+    crate::custom_rewards::CustomRewardsModule
+    + crate::rewards::RewardsModule
+    + crate::config::ConfigModule
+    + token_send::TokenSendModule
+    + crate::tokens::farm_token::FarmTokenModule
+    + crate::tokens::request_id::RequestIdModule
+    + utils::UtilsModule
+    + permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::tiered_rewards::read_config::ReadConfigModule
+    + crate::tiered_rewards::total_tokens::TokenPerTierModule
+    + crate::tiered_rewards::call_config::CallConfigModule
+    + crate::user_actions::close_guild::CloseGuildModule
+    + crate::user_actions::unbond_epochs::UnbondEpochsModule
+    + crate::boosted_yields::BoostedYieldsModule
+{
+    /// Officers are rewarded at the guild master's tier, instead of the user tiers and boosted yields.
+    /// They need the guild master's min stake, and unbond over the guild master's unbond period.
+    /// Rewards earned so far as a user are kept.
+    #[endpoint(addOfficer)]
+    fn add_officer(&self, officer: ManagedAddress) {
+        self.require_not_closing();
+        self.require_not_globally_paused();
+        self.require_guild_master_caller();
+
+        let guild_master = self.guild_master_address().get();
+        require!(officer != guild_master, "Invalid officer");
+        require!(self.officers().len() < MAX_OFFICERS, "Too many officers");
+        require!(!self.officers().contains(&officer), "Already an officer");
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        self.set_rps_checkpoint(&officer, &storage_cache.officer_rps, &storage_cache);
+        let _ = self.officers().insert(officer.clone());
+
+        let officer_tokens = self.user_tokens(&officer).get();
+        self.officer_tokens()
+            .update(|total| *total += &officer_tokens);
+
+        self.require_over_min_stake(&officer);
+        self.update_boosted_yields_progress(&officer);
+
+        self.officer_added_event(&officer, &officer_tokens);
+    }
+
+    /// The officer is rewarded at the user tiers again, keeping the rewards earned so far as an officer
+    #[endpoint(removeOfficer)]
+    fn remove_officer(&self, officer: ManagedAddress) {
+        self.require_not_globally_paused();
+        self.require_guild_master_caller();
+        require!(self.officers().contains(&officer), "Not an officer");
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        self.demote_officer(&officer, &storage_cache);
+    }
+
+    /// Officers whose stake dropped below the officer min stake are rewarded at the user tiers again.
+    /// The rewards must have been generated in the current block.
+    fn remove_officer_below_min_stake(&self, officer: &ManagedAddress) {
+        if !self.officers().contains(officer)
+            || self.user_tokens(officer).get() >= self.get_min_stake_guild_master()
+        {
+            return;
+        }
+
+        let storage_cache = StorageCache::new(self);
+        self.demote_officer(officer, &storage_cache);
+    }
+
+    fn demote_officer(&self, officer: &ManagedAddress, storage_cache: &StorageCache<Self>) {
+        self.set_rps_checkpoint(officer, &storage_cache.user_rps, storage_cache);
+        let _ = self.officers().swap_remove(officer);
+
+        let officer_tokens = self.user_tokens(officer).get();
+        self.officer_tokens()
+            .update(|total| *total -= &officer_tokens);
+
+        self.update_boosted_yields_progress(officer);
+
+        self.officer_removed_event(officer, &officer_tokens);
+    }
+
+    #[event("officerAddedEvent")]
+    fn officer_added_event(&self, #[indexed] officer: &ManagedAddress, staked_tokens: &BigUint);
+
+    #[event("officerRemovedEvent")]
+    fn officer_removed_event(&self, #[indexed] officer: &ManagedAddress, staked_tokens: &BigUint);
+}
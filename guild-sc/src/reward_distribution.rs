@@ -0,0 +1,165 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+/// Aggregates over all the farm positions of a member.
+/// Pending rewards are (farm_amount * rps - rps_weight) / division_safety_constant,
+/// out of which distributed_rewards were already sent by keepers.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Default)]
+pub struct MemberRewardsInfo<M: ManagedTypeApi> {
+    pub farm_amount: BigUint<M>,
+    pub rps_weight: BigUint<M>,
+    pub distributed_rewards: BigUint<M>,
+}
+
+#[multiversx_sc::module]
+pub trait RewardDistributionModule {
+    /// Migrated members keep their unsettled rewards outside the member list, until they stake again
+    fn add_position_weight(&self, member: &ManagedAddress, farm_amount: &BigUint, rps: &BigUint) {
+        if farm_amount == &0 {
+            return;
+        }
+
+        let _ = self.members().insert(member.clone());
+        let info_mapper = self.member_rewards_info(member);
+        let mut info = if !info_mapper.is_empty() {
+            info_mapper.get()
+        } else {
+            MemberRewardsInfo::default()
+        };
+        info.farm_amount += farm_amount;
+        info.rps_weight += farm_amount * rps;
+
+        info_mapper.set(info);
+    }
+
+    /// Positions created before the member list existed may not be tracked, hence the saturating math.
+    /// The distributed rewards are kept until they are settled by the member's next claims.
+    fn remove_position_weight(
+        &self,
+        member: &ManagedAddress,
+        farm_amount: &BigUint,
+        rps: &BigUint,
+    ) {
+        let info_mapper = self.member_rewards_info(member);
+        if info_mapper.is_empty() {
+            return;
+        }
+
+        let mut info = info_mapper.get();
+        let weight = farm_amount * rps;
+        if &info.farm_amount <= farm_amount {
+            info.farm_amount = BigUint::zero();
+            info.rps_weight = BigUint::zero();
+        } else {
+            info.farm_amount -= farm_amount;
+            if info.rps_weight > weight {
+                info.rps_weight -= weight;
+            } else {
+                info.rps_weight = BigUint::zero();
+            }
+        }
+
+        self.save_member_rewards_info(member, info);
+    }
+
+    /// Deducts the rewards already sent by keepers from the rewards of the claimed positions.
+    /// Returns the amount that is still owed to the member.
+    fn settle_distributed_rewards(&self, member: &ManagedAddress, rewards: BigUint) -> BigUint {
+        let info_mapper = self.member_rewards_info(member);
+        if info_mapper.is_empty() {
+            return rewards;
+        }
+
+        let mut info = info_mapper.get();
+        if info.distributed_rewards == 0 {
+            return rewards;
+        }
+
+        if rewards <= info.distributed_rewards {
+            info.distributed_rewards -= &rewards;
+            self.save_member_rewards_info(member, info);
+
+            return BigUint::zero();
+        }
+
+        let remaining_rewards = rewards - &info.distributed_rewards;
+        info.distributed_rewards = BigUint::zero();
+        self.save_member_rewards_info(member, info);
+
+        remaining_rewards
+    }
+
+    /// Members are only removed once they have no tracked positions and no unsettled rewards
+    fn save_member_rewards_info(
+        &self,
+        member: &ManagedAddress,
+        info: MemberRewardsInfo<Self::Api>,
+    ) {
+        if info.farm_amount == 0 && info.distributed_rewards == 0 {
+            self.member_rewards_info(member).clear();
+            let _ = self.members().swap_remove(member);
+
+            return;
+        }
+
+        self.member_rewards_info(member).set(info);
+    }
+
+    fn get_tracked_farm_amount(&self, member: &ManagedAddress) -> BigUint {
+        let info_mapper = self.member_rewards_info(member);
+        if info_mapper.is_empty() {
+            return BigUint::zero();
+        }
+
+        info_mapper.get().farm_amount
+    }
+
+    /// Returns the rewards not yet distributed, and marks them as distributed
+    fn take_undistributed_rewards(
+        &self,
+        member: &ManagedAddress,
+        rps: &BigUint,
+        division_safety_constant: &BigUint,
+    ) -> BigUint {
+        let info_mapper = self.member_rewards_info(member);
+        let mut info = info_mapper.get();
+        let total_weight = &info.farm_amount * rps;
+        if total_weight <= info.rps_weight {
+            return BigUint::zero();
+        }
+
+        let total_rewards = (total_weight - &info.rps_weight) / division_safety_constant;
+        if total_rewards <= info.distributed_rewards {
+            return BigUint::zero();
+        }
+
+        let undistributed_rewards = &total_rewards - &info.distributed_rewards;
+        info.distributed_rewards = total_rewards;
+        info_mapper.set(info);
+
+        undistributed_rewards
+    }
+
+    #[view(getDistributedRewards)]
+    fn get_distributed_rewards(&self, member: ManagedAddress) -> BigUint {
+        let info_mapper = self.member_rewards_info(&member);
+        if info_mapper.is_empty() {
+            return BigUint::zero();
+        }
+
+        info_mapper.get().distributed_rewards
+    }
+
+    #[view(getMembers)]
+    #[storage_mapper("members")]
+    fn members(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("memberRewardsInfo")]
+    fn member_rewards_info(
+        &self,
+        member: &ManagedAddress,
+    ) -> SingleValueMapper<MemberRewardsInfo<Self::Api>>;
+
+    #[storage_mapper("distributionCursor")]
+    fn distribution_cursor(&self) -> SingleValueMapper<usize>;
+}
@@ -1,6 +1,15 @@
 use crate::contexts::storage_cache::StorageCache;
 
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+/// Set when a member becomes or stops being an officer, so their rps continues from `base_rps`,
+/// increasing with their new class rps from `class_rps` onwards
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct RpsCheckpoint<M: ManagedTypeApi> {
+    pub base_rps: BigUint<M>,
+    pub class_rps: BigUint<M>,
+}
 
 #[multiversx_sc::module]
 pub trait RewardsModule:
@@ -25,32 +34,82 @@ pub trait RewardsModule:
         self.last_reward_block_nonce().set(current_nonce);
     }
 
+    fn end_produce_rewards(&self) {
+        require!(
+            self.produce_rewards_enabled().get(),
+            "Producing rewards is already disabled"
+        );
+        self.produce_rewards_enabled().set(false);
+    }
+
     #[inline]
     fn produces_per_block_rewards(&self) -> bool {
         self.produce_rewards_enabled().get()
     }
 
-    fn get_rps_by_user<'a>(
+    fn get_rps_by_user(
+        &self,
+        user: &ManagedAddress,
+        storage_cache: &StorageCache<Self>,
+    ) -> BigUint {
+        let guild_master = self.guild_master_address().get();
+        if user == &guild_master {
+            return storage_cache.guild_master_rps.clone();
+        }
+
+        let checkpoint_mapper = self.rps_checkpoint(user);
+        if checkpoint_mapper.is_empty() {
+            return storage_cache.user_rps.clone();
+        }
+
+        let checkpoint = checkpoint_mapper.get();
+        let class_rps = self.get_class_rps(user, storage_cache);
+
+        checkpoint.base_rps + class_rps - checkpoint.class_rps
+    }
+
+    fn get_class_rps<'a>(
         &self,
         user: &ManagedAddress,
         storage_cache: &'a StorageCache<Self>,
     ) -> &'a BigUint {
-        let guild_master = self.guild_master_address().get();
-        if user != &guild_master {
-            &storage_cache.user_rps
+        if self.officers().contains(user) {
+            &storage_cache.officer_rps
         } else {
-            &storage_cache.guild_master_rps
+            &storage_cache.user_rps
         }
     }
 
+    /// Must be called right before the user's class changes, after the rewards were generated
+    fn set_rps_checkpoint(
+        &self,
+        user: &ManagedAddress,
+        new_class_rps: &BigUint,
+        storage_cache: &StorageCache<Self>,
+    ) {
+        let base_rps = self.get_rps_by_user(user, storage_cache);
+        self.rps_checkpoint(user).set(RpsCheckpoint {
+            base_rps,
+            class_rps: new_class_rps.clone(),
+        });
+    }
+
     #[view(getGuildMasterRewardPerShare)]
     #[storage_mapper("guildMasterRps")]
     fn guild_master_rps(&self) -> SingleValueMapper<BigUint>;
 
+    #[view(getOfficerRewardPerShare)]
+    #[storage_mapper("officerRps")]
+    fn officer_rps(&self) -> SingleValueMapper<BigUint>;
+
     #[view(getUserRewardPerShare)]
     #[storage_mapper("userRps")]
     fn user_rps(&self) -> SingleValueMapper<BigUint>;
 
+    #[view(getRpsCheckpoint)]
+    #[storage_mapper("rpsCheckpoint")]
+    fn rps_checkpoint(&self, user: &ManagedAddress) -> SingleValueMapper<RpsCheckpoint<Self::Api>>;
+
     #[view(getRewardReserve)]
     #[storage_mapper("reward_reserve")]
     fn reward_reserve(&self) -> SingleValueMapper<BigUint>;
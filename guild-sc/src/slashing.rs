@@ -0,0 +1,92 @@
+multiversx_sc::imports!();
+
+use common_structs::Percent;
+
+use crate::contexts::storage_cache::StorageCache;
+use crate::custom_rewards::MAX_PERCENT;
+use crate::farm_base_impl::base_traits_impl::{FarmContract, FarmStakingWrapper};
+
+pub const MAX_SLASH_PERCENTAGE: Percent = 2_000;
+
+#[multiversx_sc::module]
+pub trait SlashingModule:
+    crate::custom_rewards::CustomRewardsModule
+    + crate::rewards::RewardsModule
+    + crate::config::ConfigModule
+    + token_send::TokenSendModule
+    + crate::tokens::farm_token::FarmTokenModule
+    + crate::tokens::request_id::RequestIdModule
+    + utils::UtilsModule
+    + permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::tiered_rewards::read_config::ReadConfigModule
+    + crate::tiered_rewards::total_tokens::TokenPerTierModule
+    + crate::tiered_rewards::call_config::CallConfigModule
+    + crate::user_actions::close_guild::CloseGuildModule
+    + crate::boosted_yields::BoostedYieldsModule
+    + crate::user_actions::custom_events::CustomEventsModule
+{
+    /// Removes a percentage of the guild master's stake, at most MAX_SLASH_PERCENTAGE per call.
+    /// The slashed tokens are sent to the treasury if given, otherwise they are distributed to the members.
+    /// The guild master's farm tokens are kept, but the slashed tokens are not rewarded and are deducted on unstake.
+    #[only_owner]
+    #[endpoint(slashGuildMaster)]
+    fn slash_guild_master(
+        &self,
+        percentage: Percent,
+        opt_treasury: OptionalValue<ManagedAddress>,
+    ) -> BigUint {
+        self.require_not_closing();
+        require!(
+            percentage > 0 && percentage <= MAX_SLASH_PERCENTAGE,
+            "Invalid slash percentage"
+        );
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        let guild_master_tokens = self.guild_master_tokens().get();
+        let slashed_amount = &guild_master_tokens * percentage / MAX_PERCENT;
+        require!(slashed_amount > 0, "Nothing to slash");
+
+        let opt_treasury = opt_treasury.into_option();
+        match &opt_treasury {
+            Some(treasury) => {
+                self.send().direct_esdt(
+                    treasury,
+                    &storage_cache.farming_token_id,
+                    0,
+                    &slashed_amount,
+                );
+            }
+            None => {
+                let total_user_tokens = &storage_cache.farm_token_supply
+                    - &self.get_guild_master_farm_tokens()
+                    - &self.officer_tokens().get();
+                require!(total_user_tokens > 0, "No members to compensate");
+
+                // farming and reward token are the same, so the slashed tokens are added to the rewards
+                storage_cache.user_rps +=
+                    &slashed_amount * &storage_cache.division_safety_constant / total_user_tokens;
+                storage_cache.reward_reserve += &slashed_amount;
+            }
+        }
+
+        self.guild_master_tokens()
+            .set(&guild_master_tokens - &slashed_amount);
+        self.slashed_guild_master_tokens()
+            .update(|slashed| *slashed += &slashed_amount);
+        self.remove_total_base_staked_tokens(&slashed_amount);
+        self.call_decrease_total_staked_tokens(slashed_amount.clone());
+
+        let guild_master = self.guild_master_address().get();
+        self.emit_slash_guild_master_event(
+            &guild_master,
+            percentage,
+            &slashed_amount,
+            opt_treasury,
+        );
+
+        slashed_amount
+    }
+}
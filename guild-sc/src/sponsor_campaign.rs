@@ -0,0 +1,245 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use common_structs::{Epoch, Nonce, Percent};
+
+use crate::contexts::storage_cache::StorageCache;
+use crate::custom_rewards::{MAX_PERCENT, SECONDS_IN_YEAR};
+
+pub const MAX_CAMPAIGN_EPOCHS: Epoch = 365;
+pub const MAX_CAMPAIGN_APR: Percent = 10_000;
+pub const SECONDS_IN_EPOCH: u64 = 86_400;
+
+/// Rewards are produced from `last_reward_block_nonce` until `end_block_nonce`,
+/// the block expected at `end_epoch`
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct SponsorCampaign<M: ManagedTypeApi> {
+    pub sponsor: ManagedAddress<M>,
+    pub extra_apr: Percent,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    pub end_block_nonce: Nonce,
+    pub deposited: BigUint<M>,
+    pub distributed: BigUint<M>,
+    pub last_reward_block_nonce: Nonce,
+}
+
+#[multiversx_sc::module]
+pub trait SponsorCampaignModule:
+    crate::rewards::RewardsModule
+    + crate::config::ConfigModule
+    + crate::tokens::farm_token::FarmTokenModule
+    + crate::tokens::request_id::RequestIdModule
+    + permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::tiered_rewards::read_config::ReadConfigModule
+    + crate::tiered_rewards::total_tokens::TokenPerTierModule
+    + crate::user_actions::close_guild::CloseGuildModule
+{
+    /// Anyone may sponsor the guild's members with an extra APR, on top of the tier APR, for `duration_epochs`.
+    /// The payment is the campaign budget, which must cover the extra APR for the whole duration at the current stake.
+    /// Only one campaign may be active at a time. The guild master may replace their own campaign,
+    /// or a campaign whose remaining budget no longer covers the rest of its duration.
+    #[payable("*")]
+    #[endpoint(startSponsorCampaign)]
+    fn start_sponsor_campaign(&self, extra_apr: Percent, duration_epochs: Epoch) {
+        self.require_not_closing();
+        self.require_not_globally_paused();
+
+        let caller = self.blockchain().get_caller();
+        if !self.sponsor_campaign().is_empty() {
+            require!(
+                caller == self.guild_master_address().get(),
+                "Campaign already active"
+            );
+
+            let campaign = self.take_sponsor_campaign();
+            require!(
+                campaign.sponsor == caller || self.is_sponsor_campaign_underfunded(&campaign),
+                "Campaign may not be replaced"
+            );
+            let _ = self.refund_sponsor_campaign(campaign);
+        }
+
+        require!(
+            extra_apr > 0 && extra_apr <= MAX_CAMPAIGN_APR,
+            "Invalid campaign APR"
+        );
+        require!(
+            duration_epochs > 0 && duration_epochs <= MAX_CAMPAIGN_EPOCHS,
+            "Invalid campaign duration"
+        );
+
+        let (payment_token, payment_amount) = self.call_value().single_fungible_esdt();
+        require!(
+            payment_token == self.reward_token_id().get(),
+            "Invalid token"
+        );
+
+        let duration_blocks = duration_epochs * SECONDS_IN_EPOCH / self.get_seconds_per_block();
+        let member_tokens = self.farm_token_supply().get() - self.get_guild_master_farm_tokens();
+        let min_budget =
+            self.get_sponsor_campaign_rewards(&member_tokens, extra_apr, duration_blocks);
+        require!(
+            payment_amount > 0 && payment_amount >= min_budget,
+            "Campaign budget too low"
+        );
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let current_block_nonce = self.blockchain().get_block_nonce();
+        let campaign = SponsorCampaign {
+            sponsor: caller.clone(),
+            extra_apr,
+            start_epoch: current_epoch,
+            end_epoch: current_epoch + duration_epochs,
+            end_block_nonce: current_block_nonce + duration_blocks,
+            deposited: payment_amount,
+            distributed: BigUint::zero(),
+            last_reward_block_nonce: current_block_nonce,
+        };
+        self.sponsor_campaign_started_event(&caller, &campaign);
+        self.sponsor_campaign().set(campaign);
+    }
+
+    /// Anyone may end the campaign after its end epoch, or once the guild is closing.
+    /// The rewards left are refunded to the sponsor.
+    #[endpoint(endSponsorCampaign)]
+    fn end_sponsor_campaign(&self) -> BigUint {
+        require!(!self.sponsor_campaign().is_empty(), "No active campaign");
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let campaign_end_epoch = self.sponsor_campaign().get().end_epoch;
+        require!(
+            current_epoch >= campaign_end_epoch || self.guild_closing().get(),
+            "Campaign not over"
+        );
+
+        let campaign = self.take_sponsor_campaign();
+        self.refund_sponsor_campaign(campaign)
+    }
+
+    /// The campaign rewards are produced up to the current block before the campaign is removed
+    fn take_sponsor_campaign(&self) -> SponsorCampaign<Self::Api> {
+        let mut storage_cache = StorageCache::new(self);
+        self.generate_sponsor_campaign_rewards(&mut storage_cache);
+
+        self.sponsor_campaign().take()
+    }
+
+    fn refund_sponsor_campaign(&self, campaign: SponsorCampaign<Self::Api>) -> BigUint {
+        let refund = &campaign.deposited - &campaign.distributed;
+        if refund > 0 {
+            let reward_token_id = self.reward_token_id().get();
+            self.send()
+                .direct_esdt(&campaign.sponsor, &reward_token_id, 0, &refund);
+        }
+
+        self.sponsor_campaign_ended_event(&campaign.sponsor, &campaign.distributed, &refund);
+
+        refund
+    }
+
+    /// The rewards left do not cover the extra APR until the end of the campaign, at the current stake
+    fn is_sponsor_campaign_underfunded(&self, campaign: &SponsorCampaign<Self::Api>) -> bool {
+        let current_block_nonce = self.blockchain().get_block_nonce();
+        if current_block_nonce >= campaign.end_block_nonce {
+            return true;
+        }
+
+        let member_tokens = self.farm_token_supply().get() - self.get_guild_master_farm_tokens();
+        let remaining_blocks = campaign.end_block_nonce - current_block_nonce;
+        let remaining_rewards = &campaign.deposited - &campaign.distributed;
+
+        remaining_rewards
+            < self.get_sponsor_campaign_rewards(
+                &member_tokens,
+                campaign.extra_apr,
+                remaining_blocks,
+            )
+    }
+
+    /// Members other than the guild master receive the campaign rewards, bounded by the campaign APR
+    /// and the rewards left in the campaign
+    fn generate_sponsor_campaign_rewards(&self, storage_cache: &mut StorageCache<Self>) {
+        let campaign_mapper = self.sponsor_campaign();
+        if campaign_mapper.is_empty() {
+            return;
+        }
+
+        let mut campaign = campaign_mapper.get();
+        let current_block_nonce = core::cmp::min(
+            self.blockchain().get_block_nonce(),
+            campaign.end_block_nonce,
+        );
+        if current_block_nonce <= campaign.last_reward_block_nonce {
+            return;
+        }
+
+        let block_nonce_diff = current_block_nonce - campaign.last_reward_block_nonce;
+        campaign.last_reward_block_nonce = current_block_nonce;
+
+        let member_tokens = &storage_cache.farm_token_supply - &self.get_guild_master_farm_tokens();
+        let remaining_rewards = &campaign.deposited - &campaign.distributed;
+        if member_tokens == 0 || remaining_rewards == 0 || self.guild_closing().get() {
+            campaign_mapper.set(campaign);
+
+            return;
+        }
+
+        let rewards_bounded =
+            self.get_sponsor_campaign_rewards(&member_tokens, campaign.extra_apr, block_nonce_diff);
+        let rewards = core::cmp::min(rewards_bounded, remaining_rewards);
+
+        // users and officers earn the same extra APR
+        let increase_rps = &rewards * &storage_cache.division_safety_constant / &member_tokens;
+        storage_cache.user_rps += &increase_rps;
+        storage_cache.officer_rps += &increase_rps;
+        storage_cache.reward_reserve += &rewards;
+
+        campaign.distributed += rewards;
+        campaign_mapper.set(campaign);
+    }
+
+    /// The same model is used for the payouts and for the campaign budget
+    fn get_sponsor_campaign_rewards(
+        &self,
+        member_tokens: &BigUint,
+        extra_apr: Percent,
+        blocks: u64,
+    ) -> BigUint {
+        let seconds_per_block = self.get_seconds_per_block();
+        let blocks_in_year = SECONDS_IN_YEAR / seconds_per_block;
+
+        member_tokens * extra_apr * blocks / MAX_PERCENT / blocks_in_year
+    }
+
+    #[view(getSponsorCampaignRemainingRewards)]
+    fn get_sponsor_campaign_remaining_rewards(&self) -> BigUint {
+        let campaign_mapper = self.sponsor_campaign();
+        if campaign_mapper.is_empty() {
+            return BigUint::zero();
+        }
+
+        let campaign = campaign_mapper.get();
+        campaign.deposited - campaign.distributed
+    }
+
+    #[event("sponsorCampaignStartedEvent")]
+    fn sponsor_campaign_started_event(
+        &self,
+        #[indexed] sponsor: &ManagedAddress,
+        campaign: &SponsorCampaign<Self::Api>,
+    );
+
+    #[event("sponsorCampaignEndedEvent")]
+    fn sponsor_campaign_ended_event(
+        &self,
+        #[indexed] sponsor: &ManagedAddress,
+        #[indexed] distributed: &BigUint,
+        refund: &BigUint,
+    );
+
+    #[view(getSponsorCampaign)]
+    #[storage_mapper("sponsorCampaign")]
+    fn sponsor_campaign(&self) -> SingleValueMapper<SponsorCampaign<Self::Api>>;
+}
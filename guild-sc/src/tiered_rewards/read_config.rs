@@ -1,7 +1,13 @@
 use common_structs::{Epoch, Percent};
 use guild_sc_config::{
-    global_config::{GlobalPauseStatus, UNPAUSED},
-    tier_types::{GuildMasterRewardTier, RewardTier, UserRewardTier},
+    boosted_yields::BoostedYieldsFactors,
+    global_config::{
+        ClosedGuildExitMode, GlobalPauseStatus, ProxyTrait as _, StakedPercentAverage,
+        NO_SWAP_PAIR_ERR_MSG, UNPAUSED,
+    },
+    tier_types::{
+        interpolate_apr, GuildMasterRewardTier, RewardTier, TierCurveMode, UserRewardTier,
+    },
 };
 
 multiversx_sc::imports!();
@@ -24,6 +30,35 @@ pub trait ReadConfigModule {
         mapper.get(last_index)
     }
 
+    fn find_apr_common<T: TopEncode + TopDecode + RewardTier<Self::Api>>(
+        &self,
+        total_farming_tokens: &BigUint,
+        percentage_staked: Percent,
+        mapper: &VecMapper<T>,
+        curve_mode: TierCurveMode,
+    ) -> Percent {
+        if curve_mode == TierCurveMode::Step {
+            let tier = self.find_tier_common(total_farming_tokens, percentage_staked, mapper);
+            return tier.get_apr();
+        }
+
+        let position = T::get_position(total_farming_tokens, percentage_staked);
+        let mut opt_previous_tier = None;
+        for reward_tier in mapper.iter() {
+            if reward_tier.is_in_range(total_farming_tokens, percentage_staked) {
+                return match opt_previous_tier {
+                    Some(previous_tier) => interpolate_apr(&previous_tier, &reward_tier, &position),
+                    None => reward_tier.get_apr(),
+                };
+            }
+
+            opt_previous_tier = Some(reward_tier);
+        }
+
+        let last_index = mapper.len();
+        mapper.get(last_index).get_apr()
+    }
+
     fn get_guild_master_tiers_mapper(
         &self,
     ) -> VecMapper<GuildMasterRewardTier<Self::Api>, ManagedAddress> {
@@ -36,6 +71,17 @@ pub trait ReadConfigModule {
         self.external_user_tiers(config_addr)
     }
 
+    fn get_guild_master_tiers_curve_mode(&self) -> TierCurveMode {
+        let config_addr = self.config_sc_address().get();
+        self.external_guild_master_tiers_curve_mode(config_addr)
+            .get()
+    }
+
+    fn get_user_tiers_curve_mode(&self) -> TierCurveMode {
+        let config_addr = self.config_sc_address().get();
+        self.external_user_tiers_curve_mode(config_addr).get()
+    }
+
     fn get_max_staked_tokens(&self) -> BigUint {
         let config_addr = self.config_sc_address().get();
         self.external_max_staked_tokens(config_addr).get()
@@ -46,12 +92,52 @@ pub trait ReadConfigModule {
         self.external_min_unbond_epochs_user(config_addr).get()
     }
 
+    fn get_closed_guild_exit_mode(&self) -> ClosedGuildExitMode {
+        let config_addr = self.config_sc_address().get();
+        self.external_closed_guild_exit_mode(config_addr).get()
+    }
+
     fn get_min_unbond_epochs_guild_master(&self) -> Epoch {
         let config_addr = self.config_sc_address().get();
         self.external_min_unbond_epochs_guild_master(config_addr)
             .get()
     }
 
+    fn get_unbond_epochs_bounds(&self) -> (Epoch, Epoch) {
+        let config_addr = self.config_sc_address().get();
+        let bounds: MultiValue2<Epoch, Epoch> = self
+            .config_proxy(config_addr)
+            .get_unbond_epochs_bounds()
+            .execute_on_dest_context();
+
+        bounds.into_tuple()
+    }
+
+    fn get_governance_settings(&self) -> (Epoch, Percent) {
+        let config_addr = self.config_sc_address().get();
+        let settings: MultiValue2<Epoch, Percent> = self
+            .config_proxy(config_addr)
+            .get_governance_settings()
+            .execute_on_dest_context();
+
+        settings.into_tuple()
+    }
+
+    fn get_protocol_fee(&self) -> Percent {
+        let config_addr = self.config_sc_address().get();
+        self.external_protocol_fee(config_addr).get()
+    }
+
+    fn get_collateral_settings(&self) -> (Percent, Epoch) {
+        let config_addr = self.config_sc_address().get();
+        let collateral_ratio = self.external_collateral_ratio(config_addr.clone()).get();
+        let grace_epochs = self
+            .external_undercollateralized_grace_epochs(config_addr)
+            .get();
+
+        (collateral_ratio, grace_epochs)
+    }
+
     fn get_min_stake_user(&self) -> BigUint {
         let config_addr = self.config_sc_address().get();
         self.external_min_stake_user(config_addr).get()
@@ -64,7 +150,7 @@ pub trait ReadConfigModule {
 
     fn get_min_stake_for_user(&self, user: &ManagedAddress) -> BigUint {
         let guild_master = self.guild_master_address().get();
-        if user != &guild_master {
+        if user != &guild_master && !self.officers().contains(user) {
             self.get_min_stake_user()
         } else {
             self.get_min_stake_guild_master()
@@ -91,6 +177,13 @@ pub trait ReadConfigModule {
         self.external_total_staking_token_staked(config_addr).get()
     }
 
+    fn get_staked_percent_average_mapper(
+        &self,
+    ) -> SingleValueMapper<StakedPercentAverage, ManagedAddress> {
+        let config_addr = self.config_sc_address().get();
+        self.external_staked_percent_average(config_addr)
+    }
+
     fn get_base_farm_token_id(&self) -> ManagedBuffer {
         let config_addr = self.config_sc_address().get();
         self.external_base_farm_token_id(config_addr).get()
@@ -111,12 +204,59 @@ pub trait ReadConfigModule {
         self.external_tokens_decimals(config_addr).get()
     }
 
+    fn get_boosted_yields_factors(&self) -> BoostedYieldsFactors<Self::Api> {
+        let config_addr = self.config_sc_address().get();
+        let mapper = self.external_boosted_yields_factors(config_addr);
+        if !mapper.is_empty() {
+            mapper.get()
+        } else {
+            BoostedYieldsFactors::default()
+        }
+    }
+
+    fn get_boosted_yields_rewards_percentage(&self) -> Percent {
+        let config_addr = self.config_sc_address().get();
+        self.external_boosted_yields_rewards_percentage(config_addr)
+            .get()
+    }
+
+    fn get_energy_factory_address(&self) -> Option<ManagedAddress> {
+        let config_addr = self.config_sc_address().get();
+        let mapper = self.external_energy_factory_address(config_addr);
+        if !mapper.is_empty() {
+            Some(mapper.get())
+        } else {
+            None
+        }
+    }
+
+    fn get_swap_pair(&self, token_id: &TokenIdentifier) -> ManagedAddress {
+        let config_addr = self.config_sc_address().get();
+        let mapper = self.external_swap_pair(config_addr, token_id);
+        require!(!mapper.is_empty(), NO_SWAP_PAIR_ERR_MSG);
+
+        mapper.get()
+    }
+
     fn require_not_globally_paused(&self) {
         let config_addr = self.config_sc_address().get();
         let pause_status = self.external_global_pause_status(config_addr).get();
         require!(pause_status == UNPAUSED, "All guilds are currently paused");
     }
 
+    /// A guild paused by its members may not be entered and its rewards may not be compounded,
+    /// while exits and withdrawals keep working
+    fn require_guild_not_paused(&self) {
+        require!(!self.guild_paused().get(), "Guild paused");
+    }
+
+    fn is_globally_paused(&self) -> bool {
+        let config_addr = self.config_sc_address().get();
+        let pause_status = self.external_global_pause_status(config_addr).get();
+
+        pause_status != UNPAUSED
+    }
+
     #[proxy]
     fn config_proxy(&self, sc_address: ManagedAddress) -> guild_sc_config::Proxy<Self::Api>;
 
@@ -126,6 +266,14 @@ pub trait ReadConfigModule {
     #[storage_mapper("guildMasterAddress")]
     fn guild_master_address(&self) -> SingleValueMapper<ManagedAddress>;
 
+    #[view(isGuildPaused)]
+    #[storage_mapper("guildPaused")]
+    fn guild_paused(&self) -> SingleValueMapper<bool>;
+
+    #[view(getOfficers)]
+    #[storage_mapper("officers")]
+    fn officers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
     #[storage_mapper_from_address("guildMasterTiers")]
     fn external_guild_master_tiers(
         &self,
@@ -138,6 +286,18 @@ pub trait ReadConfigModule {
         sc_addr: ManagedAddress,
     ) -> VecMapper<UserRewardTier, ManagedAddress>;
 
+    #[storage_mapper_from_address("guildMasterTiersCurveMode")]
+    fn external_guild_master_tiers_curve_mode(
+        &self,
+        sc_addr: ManagedAddress,
+    ) -> SingleValueMapper<TierCurveMode, ManagedAddress>;
+
+    #[storage_mapper_from_address("userTiersCurveMode")]
+    fn external_user_tiers_curve_mode(
+        &self,
+        sc_addr: ManagedAddress,
+    ) -> SingleValueMapper<TierCurveMode, ManagedAddress>;
+
     #[storage_mapper_from_address("maxStakedTokens")]
     fn external_max_staked_tokens(
         &self,
@@ -150,6 +310,30 @@ pub trait ReadConfigModule {
         sc_addr: ManagedAddress,
     ) -> SingleValueMapper<Epoch, ManagedAddress>;
 
+    #[storage_mapper_from_address("closedGuildExitMode")]
+    fn external_closed_guild_exit_mode(
+        &self,
+        sc_addr: ManagedAddress,
+    ) -> SingleValueMapper<ClosedGuildExitMode, ManagedAddress>;
+
+    #[storage_mapper_from_address("protocolFee")]
+    fn external_protocol_fee(
+        &self,
+        sc_addr: ManagedAddress,
+    ) -> SingleValueMapper<Percent, ManagedAddress>;
+
+    #[storage_mapper_from_address("collateralRatio")]
+    fn external_collateral_ratio(
+        &self,
+        sc_addr: ManagedAddress,
+    ) -> SingleValueMapper<Percent, ManagedAddress>;
+
+    #[storage_mapper_from_address("undercollateralizedGraceEpochs")]
+    fn external_undercollateralized_grace_epochs(
+        &self,
+        sc_addr: ManagedAddress,
+    ) -> SingleValueMapper<Epoch, ManagedAddress>;
+
     #[storage_mapper_from_address("minUnbondEpochsGuildMaster")]
     fn external_min_unbond_epochs_guild_master(
         &self,
@@ -192,6 +376,37 @@ pub trait ReadConfigModule {
         sc_addr: ManagedAddress,
     ) -> SingleValueMapper<BigUint, ManagedAddress>;
 
+    #[storage_mapper_from_address("stakedPercentAverage")]
+    fn external_staked_percent_average(
+        &self,
+        sc_addr: ManagedAddress,
+    ) -> SingleValueMapper<StakedPercentAverage, ManagedAddress>;
+
+    #[storage_mapper_from_address("boostedYieldsFactors")]
+    fn external_boosted_yields_factors(
+        &self,
+        sc_addr: ManagedAddress,
+    ) -> SingleValueMapper<BoostedYieldsFactors<Self::Api>, ManagedAddress>;
+
+    #[storage_mapper_from_address("boostedYieldsRewardsPercentage")]
+    fn external_boosted_yields_rewards_percentage(
+        &self,
+        sc_addr: ManagedAddress,
+    ) -> SingleValueMapper<Percent, ManagedAddress>;
+
+    #[storage_mapper_from_address("energyFactoryAddress")]
+    fn external_energy_factory_address(
+        &self,
+        sc_addr: ManagedAddress,
+    ) -> SingleValueMapper<ManagedAddress, ManagedAddress>;
+
+    #[storage_mapper_from_address("swapPair")]
+    fn external_swap_pair(
+        &self,
+        sc_addr: ManagedAddress,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<ManagedAddress, ManagedAddress>;
+
     #[storage_mapper_from_address("globalPauseStatus")]
     fn external_global_pause_status(
         &self,
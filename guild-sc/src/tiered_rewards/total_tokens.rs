@@ -1,6 +1,8 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+use crate::governance::ProposalId;
+
 #[multiversx_sc::module]
 pub trait TokenPerTierModule: super::read_config::ReadConfigModule {
     #[view(getUserStakedTokens)]
@@ -41,7 +43,12 @@ pub trait TokenPerTierModule: super::read_config::ReadConfigModule {
         let guild_master = self.guild_master_address().get();
         if caller != &guild_master {
             let user_tokens_mapper = self.user_tokens(caller);
+            self.snapshot_user_tokens(caller, &user_tokens_mapper);
             self.add_tokens_common(tokens, &user_tokens_mapper);
+
+            if self.officers().contains(caller) {
+                self.add_tokens_common(tokens, &self.officer_tokens());
+            }
         } else {
             let guild_master_tokens_mapper = self.guild_master_tokens();
             self.add_tokens_common(tokens, &guild_master_tokens_mapper);
@@ -55,15 +62,31 @@ pub trait TokenPerTierModule: super::read_config::ReadConfigModule {
         });
     }
 
-    fn remove_tokens(&self, caller: &ManagedAddress, tokens: &BigUint) {
+    /// Slashed guild master tokens are removed first. Returns the slashed amount removed.
+    fn remove_tokens(&self, caller: &ManagedAddress, tokens: &BigUint) -> BigUint {
         let guild_master = self.guild_master_address().get();
         if caller != &guild_master {
             let user_tokens_mapper = self.user_tokens(caller);
+            self.snapshot_user_tokens(caller, &user_tokens_mapper);
             self.remove_tokens_common(tokens, &user_tokens_mapper);
-        } else {
-            let guild_master_tokens_mapper = self.guild_master_tokens();
-            self.remove_tokens_common(tokens, &guild_master_tokens_mapper);
+
+            if self.officers().contains(caller) {
+                self.remove_tokens_common(tokens, &self.officer_tokens());
+            }
+
+            return BigUint::zero();
         }
+
+        let slashed_tokens_mapper = self.slashed_guild_master_tokens();
+        let slashed_tokens = core::cmp::min(slashed_tokens_mapper.get(), tokens.clone());
+        if slashed_tokens > 0 {
+            slashed_tokens_mapper.update(|slashed| *slashed -= &slashed_tokens);
+        }
+
+        let guild_master_tokens_mapper = self.guild_master_tokens();
+        self.remove_tokens_common(&(tokens - &slashed_tokens), &guild_master_tokens_mapper);
+
+        slashed_tokens
     }
 
     #[inline]
@@ -73,6 +96,43 @@ pub trait TokenPerTierModule: super::read_config::ReadConfigModule {
         });
     }
 
+    /// Compounded rewards are kept when a member's position moves to another guild
+    fn add_compounded_tokens(&self, user: &ManagedAddress, tokens: &BigUint) {
+        if tokens == &0 {
+            return;
+        }
+
+        self.user_compounded_tokens(user)
+            .update(|compounded_tokens| *compounded_tokens += tokens);
+    }
+
+    /// Positions created before the compounded rewards were tracked are not counted, hence the saturating math
+    fn remove_compounded_tokens(&self, user: &ManagedAddress, tokens: &BigUint) {
+        if tokens == &0 {
+            return;
+        }
+
+        self.user_compounded_tokens(user)
+            .update(|compounded_tokens| {
+                if &*compounded_tokens > tokens {
+                    *compounded_tokens -= tokens;
+                } else {
+                    *compounded_tokens = BigUint::zero();
+                }
+            });
+    }
+
+    /// Keeps the user's tokens from before their first change during each active proposal,
+    /// so the votes use the tokens the users had when the proposal was created
+    fn snapshot_user_tokens(&self, user: &ManagedAddress, mapper: &SingleValueMapper<BigUint>) {
+        for proposal_id in self.active_proposals().iter() {
+            let snapshot_mapper = self.user_tokens_snapshot(proposal_id, user);
+            if snapshot_mapper.is_empty() {
+                snapshot_mapper.set(Some(mapper.get()));
+            }
+        }
+    }
+
     fn get_total_stake_for_user(&self, user: &ManagedAddress) -> BigUint {
         let guild_master = self.guild_master_address().get();
         if user != &guild_master {
@@ -82,6 +142,11 @@ pub trait TokenPerTierModule: super::read_config::ReadConfigModule {
         }
     }
 
+    /// The guild master's farm tokens still include the slashed tokens, until they unstake
+    fn get_guild_master_farm_tokens(&self) -> BigUint {
+        self.guild_master_tokens().get() + self.slashed_guild_master_tokens().get()
+    }
+
     fn require_over_min_stake(&self, user: &ManagedAddress) {
         let total_stake = self.get_total_stake_for_user(user);
         let guild_master = self.guild_master_address().get();
@@ -99,6 +164,36 @@ pub trait TokenPerTierModule: super::read_config::ReadConfigModule {
     #[storage_mapper("guildMasterTokens")]
     fn guild_master_tokens(&self) -> SingleValueMapper<BigUint>;
 
+    #[view(getOfficerTokens)]
+    #[storage_mapper("officerTokens")]
+    fn officer_tokens(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getSlashedGuildMasterTokens)]
+    #[storage_mapper("slashedGuildMasterTokens")]
+    fn slashed_guild_master_tokens(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getActiveProposals)]
+    #[storage_mapper("activeProposals")]
+    fn active_proposals(&self) -> UnorderedSetMapper<ProposalId>;
+
+    /// Others may not stake on behalf of a guild master candidate, which would block their election
+    #[view(getGuildMasterCandidateProposals)]
+    #[storage_mapper("guildMasterCandidateProposals")]
+    fn guild_master_candidate_proposals(
+        &self,
+        candidate: &ManagedAddress,
+    ) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("userTokensSnapshot")]
+    fn user_tokens_snapshot(
+        &self,
+        proposal_id: ProposalId,
+        user: &ManagedAddress,
+    ) -> SingleValueMapper<Option<BigUint>>;
+
     #[storage_mapper("userTokens")]
     fn user_tokens(&self, user: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("userCompoundedTokens")]
+    fn user_compounded_tokens(&self, user: &ManagedAddress) -> SingleValueMapper<BigUint>;
 }
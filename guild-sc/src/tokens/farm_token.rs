@@ -1,6 +1,8 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+use common_structs::Nonce;
+
 #[multiversx_sc::module]
 pub trait FarmTokenModule:
     permissions_module::PermissionsModule
@@ -50,6 +52,14 @@ pub trait FarmTokenModule:
         }
     }
 
+    fn get_last_farm_token_nonce(&self) -> Nonce {
+        let sc_address = self.blockchain().get_sc_address();
+        let farm_token_id = self.farm_token().get_token_id();
+
+        self.blockchain()
+            .get_current_esdt_nft_nonce(&sc_address, &farm_token_id)
+    }
+
     #[storage_mapper("farmTokenTransferRoleSet")]
     fn farm_token_transfer_role_set(&self) -> SingleValueMapper<bool>;
 
@@ -21,33 +21,86 @@ pub trait ClaimStakeFarmRewardsModule:
     + crate::tiered_rewards::read_config::ReadConfigModule
     + crate::tiered_rewards::total_tokens::TokenPerTierModule
     + super::close_guild::CloseGuildModule
+    + super::custom_events::CustomEventsModule
+    + super::swap::SwapModule
 {
     #[payable("*")]
     #[endpoint(claimRewards)]
     fn claim_rewards(&self) -> ClaimRewardsResultType<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        self.claim_rewards_common(caller, None)
+    }
+
+    /// The new farm token is sent back to the caller, while the rewards are sent to the receiver
+    #[payable("*")]
+    #[endpoint(claimRewardsTo)]
+    fn claim_rewards_to(
+        &self,
+        rewards_receiver: ManagedAddress,
+    ) -> ClaimRewardsResultType<Self::Api> {
+        require!(!rewards_receiver.is_zero(), "Invalid rewards receiver");
+
+        self.claim_rewards_common(rewards_receiver, None)
+    }
+
+    /// Swaps the rewards to the output token, through the pair set in the config SC
+    #[payable("*")]
+    #[endpoint(claimRewardsAndSwap)]
+    fn claim_rewards_and_swap(
+        &self,
+        output_token_id: TokenIdentifier,
+        min_output_amount: BigUint,
+    ) -> ClaimRewardsResultType<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        self.claim_rewards_common(caller, Some((output_token_id, min_output_amount)))
+    }
+
+    fn claim_rewards_common(
+        &self,
+        rewards_receiver: ManagedAddress,
+        opt_swap_args: Option<(TokenIdentifier, BigUint)>,
+    ) -> ClaimRewardsResultType<Self::Api> {
         self.require_not_closing();
         self.require_not_globally_paused();
 
         let caller = self.blockchain().get_caller();
         let payments = self.get_non_empty_payments();
-        let claim_result =
+        self.require_not_migrated(&caller, &payments);
+        let mut claim_result =
             self.claim_rewards_base::<FarmStakingWrapper<Self>>(caller.clone(), payments);
+        let boosted_rewards = self
+            .claim_boosted_yields_rewards(&caller, &mut claim_result.storage_cache.reward_reserve);
 
         let reward_token_id = self.reward_token_id().get();
-        let base_rewards_payment = EsdtTokenPayment::new(reward_token_id, 0, claim_result.rewards);
+        let base_rewards_payment =
+            EsdtTokenPayment::new(reward_token_id, 0, claim_result.rewards + boosted_rewards);
 
         self.send_payment_non_zero(&caller, &claim_result.new_farm_token.payment);
-        self.send_payment_non_zero(&caller, &base_rewards_payment);
+
+        let rewards_payment = match opt_swap_args {
+            Some((output_token_id, _)) if base_rewards_payment.amount == 0 => {
+                EsdtTokenPayment::new(output_token_id, 0, BigUint::zero())
+            }
+            Some((output_token_id, min_output_amount)) => self.swap_from_farming_token(
+                &caller,
+                base_rewards_payment.clone(),
+                output_token_id,
+                min_output_amount,
+            ),
+            None => base_rewards_payment.clone(),
+        };
+        self.send_payment_non_zero(&rewards_receiver, &rewards_payment);
 
         self.emit_claim_rewards_event(
             &caller,
             claim_result.context,
             claim_result.new_farm_token.clone(),
-            base_rewards_payment.clone(),
+            base_rewards_payment,
+            &rewards_receiver,
             claim_result.created_with_merge,
             claim_result.storage_cache,
         );
 
-        (claim_result.new_farm_token.payment, base_rewards_payment).into()
+        (claim_result.new_farm_token.payment, rewards_payment).into()
     }
 }
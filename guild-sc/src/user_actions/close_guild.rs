@@ -1,4 +1,15 @@
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use common_structs::{Nonce, PaymentsVec};
+
+/// Farm tokens up to `last_farm_token_nonce` were migrated to another guild,
+/// and only earn rewards up to `reward_per_share`
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct MigratedPosition<M: ManagedTypeApi> {
+    pub last_farm_token_nonce: Nonce,
+    pub reward_per_share: BigUint<M>,
+}
 
 #[multiversx_sc::module]
 pub trait CloseGuildModule {
@@ -12,7 +23,39 @@ pub trait CloseGuildModule {
         require!(closing, "Guild not closing");
     }
 
+    /// Only the farm tokens created before the migration are rejected,
+    /// so members may stake again if the guild reopens
+    fn require_not_migrated(&self, member: &ManagedAddress, payments: &PaymentsVec<Self::Api>) {
+        let last_migrated_nonce = self.get_last_migrated_farm_token_nonce(member);
+        for payment in payments {
+            self.require_not_migrated_token(payment.token_nonce, last_migrated_nonce);
+        }
+    }
+
+    fn require_not_migrated_token(&self, token_nonce: Nonce, last_migrated_nonce: Nonce) {
+        require!(
+            token_nonce == 0 || token_nonce > last_migrated_nonce,
+            "Position was migrated to another guild"
+        );
+    }
+
+    fn get_last_migrated_farm_token_nonce(&self, member: &ManagedAddress) -> Nonce {
+        let migrated_positions_mapper = self.migrated_positions(member);
+        if migrated_positions_mapper.is_empty() {
+            return 0;
+        }
+
+        migrated_positions_mapper
+            .get(migrated_positions_mapper.len())
+            .last_farm_token_nonce
+    }
+
     #[view(isGuildClosing)]
     #[storage_mapper("guildClosing")]
     fn guild_closing(&self) -> SingleValueMapper<bool>;
+
+    #[view(getMigratedPositions)]
+    #[storage_mapper("migratedPositions")]
+    fn migrated_positions(&self, member: &ManagedAddress)
+        -> VecMapper<MigratedPosition<Self::Api>>;
 }
@@ -27,9 +27,11 @@ pub trait CompoundStakeFarmRewardsModule:
     fn compound_rewards(&self) -> EsdtTokenPayment {
         self.require_not_closing();
         self.require_not_globally_paused();
+        self.require_guild_not_paused();
 
         let caller = self.blockchain().get_caller();
         let payments = self.get_non_empty_payments();
+        self.require_not_migrated(&caller, &payments);
         let compound_result =
             self.compound_rewards_base::<FarmStakingWrapper<Self>>(caller.clone(), payments);
 
@@ -37,6 +39,8 @@ pub trait CompoundStakeFarmRewardsModule:
         self.send_payment_non_zero(&caller, &new_farm_token);
 
         self.add_tokens(&caller, &compound_result.compounded_rewards);
+        self.add_compounded_tokens(&caller, &compound_result.compounded_rewards);
+        self.update_boosted_yields_progress(&caller);
         self.call_increase_total_staked_tokens(compound_result.compounded_rewards.clone());
 
         self.emit_compound_rewards_event(
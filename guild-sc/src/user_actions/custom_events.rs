@@ -1,4 +1,7 @@
+use guild_sc_config::global_config::ClosedGuildExitMode;
+
 use crate::tokens::token_attributes::{StakingFarmTokenAttributes, UnbondSftAttributes};
+use common_structs::Percent;
 
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
@@ -17,6 +20,51 @@ pub struct MigrateToOtherFarmData<M: ManagedTypeApi> {
     pub base_rewards: EsdtTokenPayment<M>,
 }
 
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct SwitchGuildEventData<M: ManagedTypeApi> {
+    pub guild_address: ManagedAddress<M>,
+    pub total_farming_tokens: BigUint<M>,
+    pub compounded_reward: BigUint<M>,
+    pub base_rewards: EsdtTokenPayment<M>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct ReceiveSwitchedPositionEventData<M: ManagedTypeApi> {
+    pub new_farm_token: EsdtTokenPayment<M>,
+    pub attributes: StakingFarmTokenAttributes<M>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct ClosedGuildExitEventData<M: ManagedTypeApi> {
+    pub exit_mode: ClosedGuildExitMode,
+    pub output_payment: EsdtTokenPayment<M>,
+    pub base_rewards: EsdtTokenPayment<M>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct StakeForEventData<M: ManagedTypeApi> {
+    pub farming_token: EsdtTokenPayment<M>,
+    pub new_farm_token: EsdtTokenPayment<M>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct StakeWithSwapEventData<M: ManagedTypeApi> {
+    pub input_payment: EsdtTokenPayment<M>,
+    pub farming_token: EsdtTokenPayment<M>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct SwapFarmingTokensEventData<M: ManagedTypeApi> {
+    pub input_payment: EsdtTokenPayment<M>,
+    pub output_payment: EsdtTokenPayment<M>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct SlashGuildMasterEventData<M: ManagedTypeApi> {
+    pub slashed_amount: BigUint<M>,
+    pub opt_treasury: Option<ManagedAddress<M>>,
+}
+
 #[multiversx_sc::module]
 pub trait CustomEventsModule {
     fn emit_cancel_unbond_event(
@@ -58,6 +106,125 @@ pub trait CustomEventsModule {
         self.migrate_to_other_farm_event(caller, &event_data);
     }
 
+    fn emit_switch_guild_event(
+        &self,
+        caller: &ManagedAddress,
+        guild_address: ManagedAddress,
+        total_farming_tokens: BigUint,
+        compounded_reward: BigUint,
+        base_rewards: EsdtTokenPayment,
+    ) {
+        let event_data = SwitchGuildEventData {
+            guild_address,
+            total_farming_tokens,
+            compounded_reward,
+            base_rewards,
+        };
+        self.switch_guild_event(caller, &event_data);
+    }
+
+    fn emit_receive_switched_position_event(
+        &self,
+        caller: &ManagedAddress,
+        new_farm_token: EsdtTokenPayment,
+        attributes: StakingFarmTokenAttributes<Self::Api>,
+    ) {
+        let event_data = ReceiveSwitchedPositionEventData {
+            new_farm_token,
+            attributes,
+        };
+        self.receive_switched_position_event(caller, &event_data);
+    }
+
+    fn emit_closed_guild_exit_event(
+        &self,
+        caller: &ManagedAddress,
+        exit_mode: ClosedGuildExitMode,
+        output_payment: EsdtTokenPayment,
+        base_rewards: EsdtTokenPayment,
+    ) {
+        let event_data = ClosedGuildExitEventData {
+            exit_mode,
+            output_payment,
+            base_rewards,
+        };
+        self.closed_guild_exit_event(caller, &event_data);
+    }
+
+    fn emit_stake_for_event(
+        &self,
+        payer: &ManagedAddress,
+        beneficiary: &ManagedAddress,
+        farming_token: EsdtTokenPayment,
+        new_farm_token: EsdtTokenPayment,
+    ) {
+        let event_data = StakeForEventData {
+            farming_token,
+            new_farm_token,
+        };
+        self.stake_for_event(payer, beneficiary, &event_data);
+    }
+
+    fn emit_stake_with_swap_event(
+        &self,
+        caller: &ManagedAddress,
+        input_payment: EsdtTokenPayment,
+        farming_token: EsdtTokenPayment,
+    ) {
+        let event_data = StakeWithSwapEventData {
+            input_payment,
+            farming_token,
+        };
+        self.stake_with_swap_event(caller, &event_data);
+    }
+
+    fn emit_swap_farming_tokens_event(
+        &self,
+        caller: &ManagedAddress,
+        input_payment: EsdtTokenPayment,
+        output_payment: EsdtTokenPayment,
+    ) {
+        let event_data = SwapFarmingTokensEventData {
+            input_payment,
+            output_payment,
+        };
+        self.swap_farming_tokens_event(caller, &event_data);
+    }
+
+    #[inline]
+    fn emit_distribute_rewards_event(
+        &self,
+        keeper: &ManagedAddress,
+        members_count: usize,
+        total_rewards: &BigUint,
+    ) {
+        self.distribute_rewards_event(keeper, members_count, total_rewards);
+    }
+
+    #[inline]
+    fn emit_migrate_members_event(
+        &self,
+        caller: &ManagedAddress,
+        members_count: usize,
+        total_farming_tokens: &BigUint,
+    ) {
+        self.migrate_members_event(caller, members_count, total_farming_tokens);
+    }
+
+    fn emit_slash_guild_master_event(
+        &self,
+        guild_master: &ManagedAddress,
+        percentage: Percent,
+        slashed_amount: &BigUint,
+        opt_treasury: Option<ManagedAddress>,
+    ) {
+        let event_data = SlashGuildMasterEventData {
+            slashed_amount: slashed_amount.clone(),
+            opt_treasury,
+        };
+        self.slash_guild_master_event(guild_master, percentage, &event_data);
+    }
+
     #[event("cancelUnbondEvent")]
     fn cancel_unbond_event(
         &self,
@@ -78,4 +245,78 @@ pub trait CustomEventsModule {
         #[indexed] caller: &ManagedAddress,
         event_data: &MigrateToOtherFarmData<Self::Api>,
     );
+
+    #[event("switchGuildEvent")]
+    fn switch_guild_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        event_data: &SwitchGuildEventData<Self::Api>,
+    );
+
+    #[event("receiveSwitchedPositionEvent")]
+    fn receive_switched_position_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        event_data: &ReceiveSwitchedPositionEventData<Self::Api>,
+    );
+
+    #[event("closedGuildExitEvent")]
+    fn closed_guild_exit_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        event_data: &ClosedGuildExitEventData<Self::Api>,
+    );
+
+    #[event("stakeWithSwapEvent")]
+    fn stake_with_swap_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        event_data: &StakeWithSwapEventData<Self::Api>,
+    );
+
+    #[event("swapFarmingTokensEvent")]
+    fn swap_farming_tokens_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        event_data: &SwapFarmingTokensEventData<Self::Api>,
+    );
+
+    #[event("distributeRewardsEvent")]
+    fn distribute_rewards_event(
+        &self,
+        #[indexed] keeper: &ManagedAddress,
+        #[indexed] members_count: usize,
+        total_rewards: &BigUint,
+    );
+
+    #[event("migrateMembersEvent")]
+    fn migrate_members_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] members_count: usize,
+        total_farming_tokens: &BigUint,
+    );
+
+    #[event("guildReopenedEvent")]
+    fn guild_reopened_event(
+        &self,
+        #[indexed] guild_master: &ManagedAddress,
+        new_farm_token: &EsdtTokenPayment,
+    );
+
+    #[event("stakeForEvent")]
+    fn stake_for_event(
+        &self,
+        #[indexed] payer: &ManagedAddress,
+        #[indexed] beneficiary: &ManagedAddress,
+        event_data: &StakeForEventData<Self::Api>,
+    );
+
+    #[event("slashGuildMasterEvent")]
+    fn slash_guild_master_event(
+        &self,
+        #[indexed] guild_master: &ManagedAddress,
+        #[indexed] percentage: Percent,
+        event_data: &SlashGuildMasterEventData<Self::Api>,
+    );
 }
@@ -0,0 +1,112 @@
+multiversx_sc::imports!();
+
+use crate::{
+    contexts::storage_cache::StorageCache,
+    farm_base_impl::base_traits_impl::{FarmContract, FarmStakingWrapper},
+};
+
+pub const MAX_DISTRIBUTION_PAGE_SIZE: usize = 50;
+
+#[multiversx_sc::module]
+pub trait DistributeRewardsModule:
+    crate::custom_rewards::CustomRewardsModule
+    + crate::rewards::RewardsModule
+    + crate::config::ConfigModule
+    + token_send::TokenSendModule
+    + crate::tokens::farm_token::FarmTokenModule
+    + crate::tokens::request_id::RequestIdModule
+    + permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + utils::UtilsModule
+    + crate::tiered_rewards::read_config::ReadConfigModule
+    + crate::tiered_rewards::total_tokens::TokenPerTierModule
+    + crate::reward_distribution::RewardDistributionModule
+    + super::custom_events::CustomEventsModule
+    + super::close_guild::CloseGuildModule
+{
+    /// Sends the accrued rewards to the next `max_members` members, without them having to send their farm tokens.
+    /// May be called by anyone. Distributed rewards are deducted from the member's next claim.
+    #[endpoint(distributeRewards)]
+    fn distribute_rewards(&self, max_members: usize) -> BigUint {
+        self.require_not_closing();
+        self.require_not_globally_paused();
+        require!(
+            max_members > 0 && max_members <= MAX_DISTRIBUTION_PAGE_SIZE,
+            "Invalid number of members"
+        );
+
+        let members_mapper = self.members();
+        let members_len = members_mapper.len();
+        if members_len == 0 {
+            return BigUint::zero();
+        }
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        let page_size = core::cmp::min(max_members, members_len);
+        let mut index = self.distribution_cursor().get();
+        let mut total_distributed = BigUint::zero();
+        for _ in 0..page_size {
+            if index >= members_len {
+                index = 0;
+            }
+
+            let member = members_mapper.get_by_index(index + 1);
+            index += 1;
+
+            let rps = self.get_rps_by_user(&member, &storage_cache);
+            let rewards = self.take_undistributed_rewards(
+                &member,
+                &rps,
+                &storage_cache.division_safety_constant,
+            );
+            if rewards == 0 {
+                continue;
+            }
+
+            self.send()
+                .direct_esdt(&member, &storage_cache.reward_token_id, 0, &rewards);
+            total_distributed += rewards;
+        }
+
+        self.distribution_cursor().set(index);
+        storage_cache.reward_reserve -= &total_distributed;
+
+        let caller = self.blockchain().get_caller();
+        self.emit_distribute_rewards_event(&caller, page_size, &total_distributed);
+
+        total_distributed
+    }
+
+    /// Tracks the positions created before the member list existed, so their rewards may be distributed as well.
+    /// Only the rewards accrued from now on are tracked, the older ones are paid when the member claims.
+    /// May be called by anyone, also while the guild is closing, so that these members are migrated as well.
+    #[endpoint(syncMemberRewards)]
+    fn sync_member_rewards(&self, members: MultiValueEncoded<ManagedAddress>) {
+        self.require_not_globally_paused();
+        require!(
+            members.len() <= MAX_DISTRIBUTION_PAGE_SIZE,
+            "Invalid number of members"
+        );
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        let guild_master = self.guild_master_address().get();
+        for member in members {
+            let farm_tokens = if member != guild_master {
+                self.user_tokens(&member).get()
+            } else {
+                self.get_guild_master_farm_tokens()
+            };
+            let tracked_farm_tokens = self.get_tracked_farm_amount(&member);
+            if farm_tokens <= tracked_farm_tokens {
+                continue;
+            }
+
+            let rps = self.get_rps_by_user(&member, &storage_cache);
+            self.add_position_weight(&member, &(farm_tokens - tracked_farm_tokens), &rps);
+        }
+    }
+}
@@ -10,14 +10,42 @@ mod guild_factory_proxy {
         #[endpoint(closeGuildNoRewardsRemaining)]
         fn close_guild_no_rewards_remaining(&self);
 
+        #[endpoint(reopenGuild)]
+        fn reopen_guild(&self);
+
+        #[endpoint(transferGuildMaster)]
+        fn transfer_guild_master(&self, new_guild_master: ManagedAddress);
+
         #[payable("*")]
         #[endpoint(migrateToOtherGuild)]
         fn migrate_to_other_guild(&self, guild: ManagedAddress, original_caller: ManagedAddress);
+
+        #[payable("*")]
+        #[endpoint(switchGuild)]
+        fn switch_guild(
+            &self,
+            guild: ManagedAddress,
+            original_caller: ManagedAddress,
+            compounded_reward: BigUint,
+        ) -> EsdtTokenPayment;
     }
 }
 
 multiversx_sc::imports!();
 
+use crate::contexts::storage_cache::StorageCache;
+use crate::farm_base_impl::base_traits_impl::{FarmContract, FarmStakingWrapper};
+use crate::tokens::token_attributes::{
+    LocalFarmToken, StakingFarmTokenAttributes, UnbondSftAttributes,
+};
+use common_structs::{Nonce, PaymentsVec};
+use fixed_supply_token::FixedSupplyToken;
+
+use super::close_guild::MigratedPosition;
+use super::distribute_rewards::MAX_DISTRIBUTION_PAGE_SIZE;
+
+pub type MigratedMember<M> = MultiValue3<ManagedAddress<M>, BigUint<M>, BigUint<M>>;
+
 #[multiversx_sc::module]
 pub trait MigrationModule:
     super::unstake_farm::UnstakeFarmModule
@@ -32,6 +60,7 @@ pub trait MigrationModule:
     + permissions_module::PermissionsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + crate::farm_base_impl::base_farm_validation::BaseFarmValidationModule
+    + crate::farm_base_impl::enter_farm::BaseEnterFarmModule
     + crate::farm_base_impl::exit_farm::BaseExitFarmModule
     + utils::UtilsModule
     + crate::tiered_rewards::read_config::ReadConfigModule
@@ -39,6 +68,8 @@ pub trait MigrationModule:
     + crate::tiered_rewards::call_config::CallConfigModule
     + super::custom_events::CustomEventsModule
     + super::close_guild::CloseGuildModule
+    + super::unbond_farm::UnbondFarmModule
+    + super::stake_farm::StakeFarmModule
 {
     #[payable("*")]
     #[endpoint(closeGuild)]
@@ -56,15 +87,17 @@ pub trait MigrationModule:
             total_payment += payment.amount;
         }
 
-        let total_guild_master_tokens = self.guild_master_tokens().get();
+        let total_guild_master_tokens = self.get_guild_master_farm_tokens();
         require!(
             total_payment == total_guild_master_tokens,
             "Must send all tokens when closing guild"
         );
 
-        self.call_decrease_total_staked_tokens(total_payment);
+        let multi_unstake_result = self.multi_unstake(&caller, &caller, &payments);
+        self.call_decrease_total_staked_tokens(
+            multi_unstake_result.farming_tokens_payment.amount.clone(),
+        );
 
-        let multi_unstake_result = self.multi_unstake(&caller, &payments);
         let unbond_epochs = self.get_min_unbond_epochs_guild_master();
         let create_unbond_token_result = self.create_and_send_unbond_tokens(
             &caller,
@@ -99,6 +132,59 @@ pub trait MigrationModule:
         self.emit_guild_closing_event(&caller, &create_unbond_token_result.attributes);
     }
 
+    /// Cancels the closing of the guild, using the unbond token received from `closeGuild`.
+    /// Only possible before the unbond period is over.
+    #[payable("*")]
+    #[endpoint(reopenGuild)]
+    fn reopen_guild(&self) -> EsdtTokenPayment {
+        self.require_closing();
+        self.require_not_globally_paused();
+
+        let guild_master = self.guild_master_address().get();
+        let caller = self.blockchain().get_caller();
+        require!(guild_master == caller, "Only guild master may reopen guild");
+
+        let unbond_token_mapper = self.unbond_token();
+        let payment = self.call_value().single_esdt();
+        unbond_token_mapper.require_same_token(&payment.token_identifier);
+
+        let unbond_attributes: UnbondSftAttributes<Self::Api> =
+            self.get_attributes_as_part_of_fixed_supply(&payment, &unbond_token_mapper);
+        require!(
+            unbond_attributes.opt_original_attributes.is_none(),
+            "Must use the unbond token received when closing"
+        );
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(
+            current_epoch < unbond_attributes.unlock_epoch,
+            "Unbond period over"
+        );
+
+        unbond_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+        self.guild_closing().set(false);
+
+        let guild_factory = self.blockchain().get_owner_address();
+        let _: IgnoreValue = self
+            .factory_proxy(guild_factory)
+            .reopen_guild()
+            .execute_on_dest_context();
+
+        self.start_produce_rewards();
+
+        let farming_token_id = self.farming_token_id().get();
+        let farming_tokens_payment = EsdtTokenPayment::new(farming_token_id, 0, payment.amount);
+        let new_farm_token = self.stake_farm_common(
+            caller.clone(),
+            PaymentsVec::from_single_item(farming_tokens_payment),
+        );
+
+        self.guild_reopened_event(&caller, &new_farm_token);
+
+        new_farm_token
+    }
+
     #[payable("*")]
     #[endpoint(migrateToOtherGuild)]
     fn migrate_to_other_guild(&self, guild_address: ManagedAddress) {
@@ -113,7 +199,7 @@ pub trait MigrationModule:
         );
 
         let payments = self.get_non_empty_payments();
-        let multi_unstake_result = self.multi_unstake(&caller, &payments);
+        let multi_unstake_result = self.multi_unstake(&caller, &caller, &payments);
         let total_farming_tokens = multi_unstake_result.farming_tokens_payment.amount.clone();
 
         self.call_decrease_total_staked_tokens(total_farming_tokens.clone());
@@ -133,6 +219,364 @@ pub trait MigrationModule:
         );
     }
 
+    /// Moves the position to another active guild, skipping the unbonding period.
+    /// Compounded rewards are kept, while base rewards are sent to the caller.
+    /// How often a member may switch is limited by a cooldown in the factory.
+    #[payable("*")]
+    #[endpoint(switchGuild)]
+    fn switch_guild(&self, guild_address: ManagedAddress) {
+        self.require_not_closing();
+        self.require_not_globally_paused();
+
+        let caller = self.blockchain().get_caller();
+        let guild_master = self.guild_master_address().get();
+        require!(
+            caller != guild_master,
+            "Guild master cannot use this endpoint"
+        );
+
+        let payments = self.get_non_empty_payments();
+        let multi_unstake_result = self.multi_unstake(&caller, &caller, &payments);
+        self.require_over_min_stake(&caller);
+
+        let total_farming_tokens = multi_unstake_result.farming_tokens_payment.amount.clone();
+        let compounded_reward = multi_unstake_result
+            .original_attributes
+            .compounded_reward
+            .clone();
+
+        self.call_decrease_total_staked_tokens(total_farming_tokens.clone());
+
+        let guild_factory = self.blockchain().get_owner_address();
+        let _: IgnoreValue = self
+            .factory_proxy(guild_factory)
+            .switch_guild(
+                guild_address.clone(),
+                caller.clone(),
+                compounded_reward.clone(),
+            )
+            .with_esdt_transfer(multi_unstake_result.farming_tokens_payment)
+            .execute_on_dest_context();
+
+        self.emit_switch_guild_event(
+            &caller,
+            guild_address,
+            total_farming_tokens,
+            compounded_reward,
+            multi_unstake_result.base_rewards_payment,
+        );
+    }
+
+    /// Restores the positions of the sent unbond tokens in another active guild, like `switchGuild`
+    #[payable("*")]
+    #[endpoint(cancelUnbondToGuild)]
+    fn cancel_unbond_to_guild(&self, guild_address: ManagedAddress) -> EsdtTokenPayment {
+        self.require_not_closing();
+        self.require_not_globally_paused();
+
+        let caller = self.blockchain().get_caller();
+        let guild_master = self.guild_master_address().get();
+        require!(
+            caller != guild_master,
+            "Guild master cannot use this endpoint"
+        );
+
+        let payments = self.get_non_empty_payments();
+        let (unbond_payments, farm_tokens) = self.split_cancel_unbond_payments(&payments);
+        require!(
+            farm_tokens.is_empty(),
+            "May only send unbond tokens when restaking in another guild"
+        );
+
+        let restored_position = self.get_restored_position(&unbond_payments);
+        let total_farming_tokens =
+            &restored_position.initial_farming_tokens + &restored_position.compounded_reward;
+
+        self.send().esdt_local_burn_multi(&unbond_payments);
+
+        let farming_token_id = self.farming_token_id().get();
+        let farming_tokens_payment =
+            EsdtTokenPayment::new(farming_token_id, 0, total_farming_tokens.clone());
+        let guild_factory = self.blockchain().get_owner_address();
+        let new_farm_token: EsdtTokenPayment = self
+            .factory_proxy(guild_factory)
+            .switch_guild(
+                guild_address.clone(),
+                caller.clone(),
+                restored_position.compounded_reward.clone(),
+            )
+            .with_esdt_transfer(farming_tokens_payment)
+            .execute_on_dest_context();
+
+        let reward_token_id = self.reward_token_id().get();
+        self.emit_switch_guild_event(
+            &caller,
+            guild_address,
+            total_farming_tokens,
+            restored_position.compounded_reward,
+            EsdtTokenPayment::new(reward_token_id, 0, BigUint::zero()),
+        );
+
+        new_farm_token
+    }
+
+    /// Receives a position switched from another guild through the factory.
+    /// The new farm token keeps the compounded rewards of the original position.
+    #[payable("*")]
+    #[endpoint(receiveSwitchedPosition)]
+    fn receive_switched_position(
+        &self,
+        original_caller: ManagedAddress,
+        compounded_reward: BigUint,
+    ) -> EsdtTokenPayment {
+        self.require_not_closing();
+        self.require_not_globally_paused();
+        self.require_guild_not_paused();
+
+        let caller = self.blockchain().get_caller();
+        let guild_factory = self.blockchain().get_owner_address();
+        require!(
+            caller == guild_factory,
+            "Only factory may call this endpoint"
+        );
+
+        let guild_master = self.guild_master_address().get();
+        require!(
+            original_caller != guild_master,
+            "Guild master cannot use this endpoint"
+        );
+        require!(
+            !self.guild_master_tokens().is_empty(),
+            "Guild master must stake first"
+        );
+
+        let payment = self.call_value().single_esdt();
+        require!(
+            compounded_reward <= payment.amount,
+            "Invalid compounded reward"
+        );
+
+        let enter_result = self.enter_farm_base_no_token_create::<FarmStakingWrapper<Self>>(
+            original_caller.clone(),
+            ManagedVec::from_single_item(payment),
+        );
+
+        let mut new_attributes = enter_result.new_farm_token.attributes;
+        new_attributes.compounded_reward = compounded_reward;
+
+        let initial_farming_tokens = new_attributes.get_initial_farming_tokens();
+        let total_farm_tokens = new_attributes.get_total_supply();
+        self.add_total_base_staked_tokens(&initial_farming_tokens);
+        self.add_tokens(&original_caller, &total_farm_tokens);
+        self.add_compounded_tokens(&original_caller, &new_attributes.compounded_reward);
+        self.update_boosted_yields_progress(&original_caller);
+        self.call_increase_total_staked_tokens(total_farm_tokens.clone());
+
+        self.require_over_min_stake(&original_caller);
+
+        let new_farm_token = self.farm_token().nft_create_and_send(
+            &original_caller,
+            total_farm_tokens,
+            &new_attributes,
+        );
+
+        self.emit_receive_switched_position_event(
+            &original_caller,
+            new_farm_token.clone(),
+            new_attributes,
+        );
+
+        new_farm_token
+    }
+
+    /// Lets the factory skip the members whose position may not be received, instead of failing the whole batch
+    #[view(canReceiveSwitchedPosition)]
+    fn can_receive_switched_position(
+        &self,
+        original_caller: ManagedAddress,
+        amount: BigUint,
+        compounded_reward: BigUint,
+    ) -> bool {
+        if self.guild_closing().get()
+            || self.is_globally_paused()
+            || self.guild_paused().get()
+            || compounded_reward > amount
+        {
+            return false;
+        }
+
+        let guild_master = self.guild_master_address().get();
+        if original_caller == guild_master
+            || self.guild_master_tokens().is_empty()
+            || self
+                .guild_master_candidate_proposals(&original_caller)
+                .get()
+                > 0
+        {
+            return false;
+        }
+
+        let initial_farming_tokens = &amount - &compounded_reward;
+        let total_base_staked_tokens = self.total_base_staked_tokens().get();
+        if total_base_staked_tokens + initial_farming_tokens > self.get_max_staked_tokens() {
+            return false;
+        }
+
+        let total_stake = self.user_tokens(&original_caller).get() + amount;
+
+        total_stake >= self.get_min_stake_for_user(&original_caller)
+    }
+
+    /// Removes the stake of the next `max_members` members of the closing guild, and sends it to the factory,
+    /// which stakes it on their behalf in another guild. Pending rewards are sent to the members.
+    /// Their farm tokens of this guild may only be used to claim the remaining rewards afterwards.
+    #[endpoint(migrateMembers)]
+    fn migrate_members(&self, max_members: usize) -> MultiValueEncoded<MigratedMember<Self::Api>> {
+        self.require_closing();
+        self.require_not_globally_paused();
+
+        let caller = self.blockchain().get_caller();
+        let guild_factory = self.blockchain().get_owner_address();
+        require!(
+            caller == guild_factory,
+            "Only factory may call this endpoint"
+        );
+        require!(
+            max_members > 0 && max_members <= MAX_DISTRIBUTION_PAGE_SIZE,
+            "Invalid number of members"
+        );
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        let guild_master = self.guild_master_address().get();
+        let last_farm_token_nonce = self.get_last_farm_token_nonce();
+        let mut migrated_members = MultiValueEncoded::new();
+        let mut total_farming_tokens = BigUint::zero();
+        let mut members_count = 0;
+        let mut index = 1;
+        while members_count < max_members && index <= self.members().len() {
+            let member = self.members().get_by_index(index);
+            if member == guild_master {
+                index += 1;
+                continue;
+            }
+
+            members_count += 1;
+
+            let rps = self.get_rps_by_user(&member, &storage_cache);
+            let mut rewards = self.take_undistributed_rewards(
+                &member,
+                &rps,
+                &storage_cache.division_safety_constant,
+            );
+            storage_cache.reward_reserve -= &rewards;
+            self.remove_migrated_member(&member);
+            self.migrated_positions(&member).push(&MigratedPosition {
+                last_farm_token_nonce,
+                reward_per_share: rps,
+            });
+
+            let staked_tokens = self.user_tokens(&member).take();
+            let compounded_tokens = core::cmp::min(
+                self.user_compounded_tokens(&member).take(),
+                staked_tokens.clone(),
+            );
+            if self.officers().swap_remove(&member) {
+                self.officer_tokens()
+                    .update(|officer_tokens| *officer_tokens -= &staked_tokens);
+            }
+            rewards +=
+                self.claim_boosted_yields_rewards(&member, &mut storage_cache.reward_reserve);
+            if rewards > 0 {
+                self.send()
+                    .direct_esdt(&member, &storage_cache.reward_token_id, 0, &rewards);
+            }
+
+            if staked_tokens == 0 {
+                continue;
+            }
+
+            self.remove_total_base_staked_tokens(&(&staked_tokens - &compounded_tokens));
+            storage_cache.farm_token_supply -= &staked_tokens;
+            total_farming_tokens += &staked_tokens;
+            migrated_members.push((member, staked_tokens, compounded_tokens).into());
+        }
+
+        if total_farming_tokens > 0 {
+            self.call_decrease_total_staked_tokens(total_farming_tokens.clone());
+            self.send().direct_esdt(
+                &guild_factory,
+                &storage_cache.farming_token_id,
+                0,
+                &total_farming_tokens,
+            );
+        }
+
+        self.emit_migrate_members_event(&caller, members_count, &total_farming_tokens);
+
+        migrated_members
+    }
+
+    /// Pays the rewards of the farm tokens left after the member was migrated,
+    /// including the positions that were not tracked in the member list.
+    /// Rewards already sent to the member are deducted.
+    #[payable("*")]
+    #[endpoint(claimMigratedRewards)]
+    fn claim_migrated_rewards(&self) -> EsdtTokenPayment {
+        self.require_not_globally_paused();
+
+        let caller = self.blockchain().get_caller();
+        let payments = self.get_non_empty_payments();
+        let division_safety_constant = self.division_safety_constant().get();
+        let farm_token_mapper = self.farm_token();
+        let mut total_rewards = BigUint::zero();
+        for payment in &payments {
+            farm_token_mapper.require_same_token(&payment.token_identifier);
+
+            let migrated_rps = self.get_migrated_position_rps(&caller, payment.token_nonce);
+            let attributes: StakingFarmTokenAttributes<Self::Api> =
+                self.get_attributes_as_part_of_fixed_supply(&payment, &farm_token_mapper);
+            if migrated_rps > attributes.reward_per_share {
+                let rps_diff = &migrated_rps - &attributes.reward_per_share;
+                total_rewards += &payment.amount * &rps_diff / &division_safety_constant;
+            }
+
+            farm_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+        }
+
+        let rewards = self.settle_distributed_rewards(&caller, total_rewards);
+        self.reward_reserve()
+            .update(|reward_reserve| *reward_reserve -= &rewards);
+
+        let reward_token_id = self.reward_token_id().get();
+        let rewards_payment = EsdtTokenPayment::new(reward_token_id, 0, rewards);
+        self.send_payment_non_zero(&caller, &rewards_payment);
+
+        rewards_payment
+    }
+
+    fn get_migrated_position_rps(&self, member: &ManagedAddress, token_nonce: Nonce) -> BigUint {
+        for migrated_position in self.migrated_positions(member).iter() {
+            if token_nonce <= migrated_position.last_farm_token_nonce {
+                return migrated_position.reward_per_share;
+            }
+        }
+
+        sc_panic!("Position was not migrated");
+    }
+
+    /// The rewards already sent to the member are kept, to be deducted from the rewards of their old farm tokens
+    fn remove_migrated_member(&self, member: &ManagedAddress) {
+        let info_mapper = self.member_rewards_info(member);
+        let mut info = info_mapper.get();
+        info.farm_amount = BigUint::zero();
+        info.rps_weight = BigUint::zero();
+        self.save_member_rewards_info(member, info);
+
+        let _ = self.members().swap_remove(member);
+    }
+
     #[proxy]
     fn factory_proxy(&self, sc_address: ManagedAddress) -> guild_factory_proxy::Proxy<Self::Api>;
 }
@@ -2,7 +2,10 @@ pub mod claim_stake_farm_rewards;
 pub mod close_guild;
 pub mod compound_stake_farm_rewards;
 pub mod custom_events;
+pub mod distribute_rewards;
 pub mod migration;
 pub mod stake_farm;
+pub mod swap;
+pub mod unbond_epochs;
 pub mod unbond_farm;
 pub mod unstake_farm;
@@ -23,6 +23,8 @@ pub trait StakeFarmModule:
     + crate::tiered_rewards::call_config::CallConfigModule
     + super::close_guild::CloseGuildModule
     + crate::tokens::unbond_token::UnbondTokenModule
+    + super::custom_events::CustomEventsModule
+    + super::swap::SwapModule
 {
     #[payable("*")]
     #[endpoint(stakeFarm)]
@@ -37,6 +39,66 @@ pub trait StakeFarmModule:
         self.stake_farm_common(original_caller, payments)
     }
 
+    /// Stakes the received farming tokens on behalf of the beneficiary.
+    /// The new farm token is sent to the beneficiary, and it may not be merged with other positions.
+    #[payable("*")]
+    #[endpoint(stakeFarmFor)]
+    fn stake_farm_for(&self, beneficiary: ManagedAddress) -> EsdtTokenPayment {
+        let payer = self.blockchain().get_caller();
+        require!(!beneficiary.is_zero(), "Invalid beneficiary");
+
+        let guild_master = self.guild_master_address().get();
+        require!(
+            beneficiary != guild_master,
+            "May not stake on behalf of guild master"
+        );
+        require!(
+            self.guild_master_candidate_proposals(&beneficiary).get() == 0,
+            "May not stake on behalf of a guild master candidate"
+        );
+
+        let payment = self.call_value().single_esdt();
+        let new_farm_token = self.stake_farm_common(
+            beneficiary.clone(),
+            PaymentsVec::from_single_item(payment.clone()),
+        );
+
+        self.emit_stake_for_event(&payer, &beneficiary, payment, new_farm_token.clone());
+
+        new_farm_token
+    }
+
+    /// Swaps the received tokens to the farming token through the pair set in the config SC,
+    /// and stakes the output. Farming tokens are staked directly.
+    #[payable("*")]
+    #[endpoint(stakeFarmWithSwap)]
+    fn stake_farm_with_swap(&self, min_farming_token_amount: BigUint) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_nonce == 0, "Only fungible tokens accepted");
+        require!(min_farming_token_amount > 0, "Invalid min amount");
+
+        let farming_token_id = self.farming_token_id().get();
+        let farming_token_payment = if payment.token_identifier == farming_token_id {
+            payment.clone()
+        } else {
+            self.swap_to_farming_token(payment.clone(), min_farming_token_amount.clone())
+        };
+        require!(
+            farming_token_payment.amount >= min_farming_token_amount,
+            "Slippage exceeded"
+        );
+
+        let new_farm_token = self.stake_farm_common(
+            caller.clone(),
+            PaymentsVec::from_single_item(farming_token_payment.clone()),
+        );
+
+        self.emit_stake_with_swap_event(&caller, payment, farming_token_payment);
+
+        new_farm_token
+    }
+
     fn stake_farm_common(
         &self,
         original_caller: ManagedAddress,
@@ -45,9 +107,11 @@ pub trait StakeFarmModule:
         self.require_not_closing();
         self.require_not_globally_paused();
         self.unbond_token().require_issued_or_set();
+        self.require_not_migrated(&original_caller, &payments);
 
         let guild_master = self.guild_master_address().get();
         if original_caller != guild_master {
+            self.require_guild_not_paused();
             require!(
                 !self.guild_master_tokens().is_empty(),
                 "Guild master must stake first"
@@ -60,6 +124,7 @@ pub trait StakeFarmModule:
         let enter_farm_amount = enter_result.context.farming_token_payment.amount.clone();
         self.add_total_base_staked_tokens(&enter_farm_amount);
         self.add_tokens(&original_caller, &enter_farm_amount);
+        self.update_boosted_yields_progress(&original_caller);
         self.call_increase_total_staked_tokens(enter_farm_amount);
 
         self.require_over_min_stake(&original_caller);
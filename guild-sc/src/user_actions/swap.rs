@@ -0,0 +1,76 @@
+multiversx_sc::imports!();
+
+use pair::pair_actions::swap::ProxyTrait as _;
+
+#[multiversx_sc::module]
+pub trait SwapModule:
+    crate::config::ConfigModule
+    + permissions_module::PermissionsModule
+    + crate::tiered_rewards::read_config::ReadConfigModule
+    + super::custom_events::CustomEventsModule
+{
+    /// Uses the pair set in the config SC for the input token
+    fn swap_to_farming_token(
+        &self,
+        payment: EsdtTokenPayment,
+        min_farming_token_amount: BigUint,
+    ) -> EsdtTokenPayment {
+        let pair_address = self.get_swap_pair(&payment.token_identifier);
+        let farming_token_id = self.farming_token_id().get();
+
+        self.swap_tokens(
+            pair_address,
+            payment,
+            farming_token_id,
+            min_farming_token_amount,
+        )
+    }
+
+    /// Uses the pair set in the config SC for the output token
+    fn swap_from_farming_token(
+        &self,
+        caller: &ManagedAddress,
+        farming_tokens: EsdtTokenPayment,
+        output_token_id: TokenIdentifier,
+        min_output_amount: BigUint,
+    ) -> EsdtTokenPayment {
+        let pair_address = self.get_swap_pair(&output_token_id);
+        let output_payment = self.swap_tokens(
+            pair_address,
+            farming_tokens.clone(),
+            output_token_id,
+            min_output_amount,
+        );
+
+        self.emit_swap_farming_tokens_event(caller, farming_tokens, output_payment.clone());
+
+        output_payment
+    }
+
+    fn swap_tokens(
+        &self,
+        pair_address: ManagedAddress,
+        payment: EsdtTokenPayment,
+        output_token_id: TokenIdentifier,
+        min_output_amount: BigUint,
+    ) -> EsdtTokenPayment {
+        require!(payment.amount > 0, "Nothing to swap");
+        require!(min_output_amount > 0, "Invalid min amount");
+
+        let output_payment: EsdtTokenPayment = self
+            .pair_proxy(pair_address)
+            .swap_tokens_fixed_input(output_token_id.clone(), min_output_amount.clone())
+            .with_esdt_transfer(payment)
+            .execute_on_dest_context();
+        require!(
+            output_payment.token_identifier == output_token_id
+                && output_payment.amount >= min_output_amount,
+            "Invalid swap output"
+        );
+
+        output_payment
+    }
+
+    #[proxy]
+    fn pair_proxy(&self, sc_address: ManagedAddress) -> pair::Proxy<Self::Api>;
+}
@@ -0,0 +1,75 @@
+use common_structs::Epoch;
+
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait UnbondEpochsModule:
+    crate::tiered_rewards::read_config::ReadConfigModule + super::close_guild::CloseGuildModule
+{
+    /// Overrides the min unbond epochs of the config SC for the users of this guild.
+    /// Must be within the unbond epochs bounds of the config SC.
+    #[endpoint(setUserUnbondEpochs)]
+    fn set_user_unbond_epochs(&self, unbond_epochs: Epoch) {
+        self.require_not_closing();
+        self.require_guild_master_caller();
+
+        let (lower_bound, upper_bound) = self.get_unbond_epochs_bounds();
+        require!(
+            unbond_epochs >= lower_bound && unbond_epochs <= upper_bound,
+            "Invalid unbond epochs"
+        );
+
+        self.user_unbond_epochs_override().set(unbond_epochs);
+
+        self.user_unbond_epochs_set_event(unbond_epochs);
+    }
+
+    /// Users will use the min unbond epochs of the config SC again
+    #[endpoint(clearUserUnbondEpochs)]
+    fn clear_user_unbond_epochs(&self) {
+        self.require_guild_master_caller();
+
+        self.user_unbond_epochs_override().clear();
+
+        self.user_unbond_epochs_cleared_event();
+    }
+
+    /// The override is clamped to the current bounds, in case they changed since it was set
+    #[view(getUserUnbondEpochs)]
+    fn get_user_unbond_epochs(&self) -> Epoch {
+        let override_mapper = self.user_unbond_epochs_override();
+        if override_mapper.is_empty() {
+            return self.get_min_unbond_epochs_user();
+        }
+
+        let (lower_bound, upper_bound) = self.get_unbond_epochs_bounds();
+        override_mapper.get().clamp(lower_bound, upper_bound)
+    }
+
+    /// Officers unbond over the guild master's unbond period, but never faster than the users of this guild
+    #[view(getOfficerUnbondEpochs)]
+    fn get_officer_unbond_epochs(&self) -> Epoch {
+        core::cmp::max(
+            self.get_user_unbond_epochs(),
+            self.get_min_unbond_epochs_guild_master(),
+        )
+    }
+
+    fn require_guild_master_caller(&self) {
+        let caller = self.blockchain().get_caller();
+        let guild_master = self.guild_master_address().get();
+        require!(
+            caller == guild_master,
+            "Only guild master may call this endpoint"
+        );
+    }
+
+    #[event("userUnbondEpochsSetEvent")]
+    fn user_unbond_epochs_set_event(&self, #[indexed] unbond_epochs: Epoch);
+
+    #[event("userUnbondEpochsClearedEvent")]
+    fn user_unbond_epochs_cleared_event(&self);
+
+    #[storage_mapper("userUnbondEpochsOverride")]
+    fn user_unbond_epochs_override(&self) -> SingleValueMapper<Epoch>;
+}
@@ -4,10 +4,16 @@ use crate::{
     contexts::storage_cache::StorageCache, farm_base_impl::base_traits_impl::FarmStakingWrapper,
     tokens::token_attributes::LocalFarmToken,
 };
+use common_structs::PaymentsVec;
 use fixed_supply_token::FixedSupplyToken;
 
 use crate::tokens::token_attributes::UnbondSftAttributes;
 
+pub struct RestoredPosition<M: ManagedTypeApi> {
+    pub initial_farming_tokens: BigUint<M>,
+    pub compounded_reward: BigUint<M>,
+}
+
 #[multiversx_sc::module]
 pub trait UnbondFarmModule:
     crate::custom_rewards::CustomRewardsModule
@@ -28,10 +34,40 @@ pub trait UnbondFarmModule:
     + crate::tiered_rewards::call_config::CallConfigModule
     + super::custom_events::CustomEventsModule
     + super::close_guild::CloseGuildModule
+    + super::swap::SwapModule
 {
     #[payable("*")]
     #[endpoint(unbondFarm)]
     fn unbond_farm(&self) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        let farming_tokens = self.unbond_farm_common();
+        self.send_payment_non_zero(&caller, &farming_tokens);
+
+        farming_tokens
+    }
+
+    /// Swaps the unbonded farming tokens to the output token, through the pair set in the config SC
+    #[payable("*")]
+    #[endpoint(unbondFarmAndSwap)]
+    fn unbond_farm_and_swap(
+        &self,
+        output_token_id: TokenIdentifier,
+        min_output_amount: BigUint,
+    ) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        let farming_tokens = self.unbond_farm_common();
+        let output_payment = self.swap_from_farming_token(
+            &caller,
+            farming_tokens,
+            output_token_id,
+            min_output_amount,
+        );
+        self.send_payment_non_zero(&caller, &output_payment);
+
+        output_payment
+    }
+
+    fn unbond_farm_common(&self) -> EsdtTokenPayment {
         let storage_cache = StorageCache::new(self);
         self.require_valid_farm_token_id(&storage_cache.farm_token_id);
 
@@ -55,74 +91,116 @@ pub trait UnbondFarmModule:
             total_farming_tokens += payment.amount;
         }
 
-        let caller = self.blockchain().get_caller();
-        let farming_tokens = EsdtTokenPayment::new(
+        EsdtTokenPayment::new(
             storage_cache.farming_token_id.clone(),
             0,
             total_farming_tokens,
-        );
-        self.send_payment_non_zero(&caller, &farming_tokens);
-
-        farming_tokens
+        )
     }
 
+    /// Restores the positions of all the sent unbond tokens in a single farm token.
+    /// Farm tokens sent along are merged into the new position.
     #[payable("*")]
     #[endpoint(cancelUnbond)]
     fn cancel_unbond(&self) -> EsdtTokenPayment {
         self.require_not_closing();
         self.require_not_globally_paused();
-
-        let unbond_token_mapper = self.unbond_token();
-        let payment = self.call_value().single_esdt();
-        unbond_token_mapper.require_same_token(&payment.token_identifier);
-
-        let unbond_attributes: UnbondSftAttributes<Self::Api> =
-            self.get_attributes_as_part_of_fixed_supply(&payment, &unbond_token_mapper);
-        require!(
-            unbond_attributes.opt_original_attributes.is_some(),
-            "May not cancel unbond for this token"
-        );
-
-        unbond_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
-
-        let original_attributes = unsafe {
-            unbond_attributes
-                .opt_original_attributes
-                .clone()
-                .unwrap_unchecked()
-        };
+        self.require_guild_not_paused();
 
         let caller = self.blockchain().get_caller();
-        let total_farming_tokens = original_attributes.get_initial_farming_tokens();
+        let payments = self.get_non_empty_payments();
+        let (unbond_payments, farm_tokens) = self.split_cancel_unbond_payments(&payments);
+        self.require_not_migrated(&caller, &farm_tokens);
+        let restored_position = self.get_restored_position(&unbond_payments);
+        let total_restored_tokens =
+            &restored_position.initial_farming_tokens + &restored_position.compounded_reward;
+
         let farming_token_id = self.farming_token_id().get();
-        let farming_token_payment =
-            EsdtTokenPayment::new(farming_token_id, 0, total_farming_tokens);
+        let mut enter_payments = ManagedVec::from_single_item(EsdtTokenPayment::new(
+            farming_token_id,
+            0,
+            total_restored_tokens.clone(),
+        ));
+        enter_payments.append_vec(farm_tokens);
         let enter_result = self.enter_farm_base_no_token_create::<FarmStakingWrapper<Self>>(
             caller.clone(),
-            ManagedVec::from_single_item(farming_token_payment),
+            enter_payments,
         );
 
         let mut new_attributes = enter_result.new_farm_token.attributes;
-        new_attributes.current_farm_amount += &original_attributes.compounded_reward;
-        new_attributes.compounded_reward = original_attributes.compounded_reward;
+        new_attributes.compounded_reward += &restored_position.compounded_reward;
 
-        let initial_farming_tokens = new_attributes.get_initial_farming_tokens();
-        self.add_total_base_staked_tokens(&initial_farming_tokens);
-        self.add_tokens(&caller, &new_attributes.get_total_supply());
-        self.call_increase_total_staked_tokens(new_attributes.get_total_supply());
+        self.add_total_base_staked_tokens(&restored_position.initial_farming_tokens);
+        self.add_tokens(&caller, &total_restored_tokens);
+        self.add_compounded_tokens(&caller, &restored_position.compounded_reward);
+        self.update_boosted_yields_progress(&caller);
+        self.call_increase_total_staked_tokens(total_restored_tokens);
 
         let total_farm_tokens = new_attributes.get_total_supply();
         let new_farm_token =
             self.farm_token()
                 .nft_create_and_send(&caller, total_farm_tokens, &new_attributes);
 
-        self.emit_cancel_unbond_event(
-            &caller,
-            unbond_attributes,
-            new_farm_token.clone(),
-            new_attributes,
-        );
+        let unbond_token_mapper = self.unbond_token();
+        for payment in &unbond_payments {
+            let unbond_attributes: UnbondSftAttributes<Self::Api> =
+                self.get_attributes_as_part_of_fixed_supply(&payment, &unbond_token_mapper);
+            unbond_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+            self.emit_cancel_unbond_event(
+                &caller,
+                unbond_attributes,
+                new_farm_token.clone(),
+                new_attributes.clone(),
+            );
+        }
 
         new_farm_token
     }
+
+    fn split_cancel_unbond_payments(
+        &self,
+        payments: &PaymentsVec<Self::Api>,
+    ) -> (PaymentsVec<Self::Api>, PaymentsVec<Self::Api>) {
+        let unbond_token_id = self.unbond_token().get_token_id();
+        let mut unbond_payments = PaymentsVec::new();
+        let mut farm_tokens = PaymentsVec::new();
+        for payment in payments {
+            if payment.token_identifier == unbond_token_id {
+                unbond_payments.push(payment);
+            } else {
+                farm_tokens.push(payment);
+            }
+        }
+
+        require!(!unbond_payments.is_empty(), "No unbond tokens sent");
+
+        (unbond_payments, farm_tokens)
+    }
+
+    /// Sums up the original positions of the unbond tokens, which are not burned yet
+    fn get_restored_position(
+        &self,
+        unbond_payments: &PaymentsVec<Self::Api>,
+    ) -> RestoredPosition<Self::Api> {
+        let unbond_token_mapper = self.unbond_token();
+        let mut restored_position = RestoredPosition {
+            initial_farming_tokens: BigUint::zero(),
+            compounded_reward: BigUint::zero(),
+        };
+        for payment in unbond_payments {
+            let unbond_attributes: UnbondSftAttributes<Self::Api> =
+                self.get_attributes_as_part_of_fixed_supply(&payment, &unbond_token_mapper);
+            let original_attributes = match unbond_attributes.opt_original_attributes {
+                Some(original_attributes) => original_attributes,
+                None => sc_panic!("May not cancel unbond for this token"),
+            };
+
+            restored_position.initial_farming_tokens +=
+                original_attributes.get_initial_farming_tokens();
+            restored_position.compounded_reward += original_attributes.compounded_reward;
+        }
+
+        restored_position
+    }
 }
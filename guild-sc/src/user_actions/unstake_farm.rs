@@ -7,6 +7,7 @@ use crate::tokens::token_attributes::LocalFarmToken;
 use common_structs::{Epoch, PaymentsVec};
 use farm::ExitFarmWithPartialPosResultType;
 use fixed_supply_token::FixedSupplyToken;
+use guild_sc_config::global_config::ClosedGuildExitMode;
 use mergeable::Mergeable;
 
 use crate::tokens::token_attributes::{StakingFarmTokenAttributes, UnbondSftAttributes};
@@ -51,20 +52,61 @@ pub trait UnstakeFarmModule:
     + crate::tiered_rewards::total_tokens::TokenPerTierModule
     + crate::tiered_rewards::call_config::CallConfigModule
     + super::close_guild::CloseGuildModule
+    + super::unbond_epochs::UnbondEpochsModule
+    + super::custom_events::CustomEventsModule
+    + crate::officers::OfficersModule
 {
     #[payable("*")]
     #[endpoint(unstakeFarm)]
     fn unstake_farm(&self) -> ExitFarmWithPartialPosResultType<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        self.unstake_farm_common(caller)
+    }
+
+    /// The unbond token is sent to the caller, while the rewards are sent to the receiver
+    #[payable("*")]
+    #[endpoint(unstakeFarmTo)]
+    fn unstake_farm_to(
+        &self,
+        rewards_receiver: ManagedAddress,
+    ) -> ExitFarmWithPartialPosResultType<Self::Api> {
+        require!(!rewards_receiver.is_zero(), "Invalid rewards receiver");
+
+        self.unstake_farm_common(rewards_receiver)
+    }
+
+    fn unstake_farm_common(
+        &self,
+        rewards_receiver: ManagedAddress,
+    ) -> ExitFarmWithPartialPosResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let payments = self.get_non_empty_payments();
-        let unstake_result = self.multi_unstake(&caller, &payments);
+        let min_unbond_epochs = if !self.officers().contains(&caller) {
+            self.get_user_unbond_epochs()
+        } else {
+            self.get_officer_unbond_epochs()
+        };
+        let unstake_result = self.multi_unstake(&caller, &rewards_receiver, &payments);
 
         self.require_over_min_stake(&caller);
 
-        let unbond_token_amount = unstake_result.farming_tokens_payment.amount;
+        let unbond_token_amount = unstake_result.farming_tokens_payment.amount.clone();
         self.call_decrease_total_staked_tokens(unbond_token_amount.clone());
 
-        let min_unbond_epochs = self.get_min_unbond_epochs_user();
+        if self.guild_closing().get() {
+            let exit_mode = self.get_closed_guild_exit_mode();
+            if exit_mode != ClosedGuildExitMode::Unbond {
+                return self.closed_guild_exit(
+                    &caller,
+                    &rewards_receiver,
+                    exit_mode,
+                    unstake_result.farming_tokens_payment,
+                    unstake_result.original_attributes,
+                    unstake_result.base_rewards_payment,
+                );
+            }
+        }
+
         let create_unbond_token_result = self.create_and_send_unbond_tokens(
             &caller,
             unbond_token_amount,
@@ -77,6 +119,7 @@ pub trait UnstakeFarmModule:
             unstake_result.original_attributes,
             create_unbond_token_result.unbond_token.clone(),
             unstake_result.base_rewards_payment.clone(),
+            &rewards_receiver,
             StorageCache::new(self),
         );
 
@@ -87,18 +130,82 @@ pub trait UnstakeFarmModule:
             .into()
     }
 
+    /// Members of closing guilds did not choose to close the guild, so they may skip the unbond period
+    fn closed_guild_exit(
+        &self,
+        caller: &ManagedAddress,
+        rewards_receiver: &ManagedAddress,
+        exit_mode: ClosedGuildExitMode,
+        farming_tokens_payment: EsdtTokenPayment,
+        original_attributes: StakingFarmTokenAttributes<Self::Api>,
+        base_rewards_payment: EsdtTokenPayment,
+    ) -> ExitFarmWithPartialPosResultType<Self::Api> {
+        let output_payment = match exit_mode {
+            ClosedGuildExitMode::DirectPayout => {
+                self.send_payment_non_zero(caller, &farming_tokens_payment);
+
+                farming_tokens_payment
+            }
+            _ => {
+                self.create_and_send_unbond_tokens(
+                    caller,
+                    farming_tokens_payment.amount,
+                    Some(original_attributes.clone()),
+                    0,
+                )
+                .unbond_token
+            }
+        };
+
+        self.emit_exit_farm_event(
+            caller,
+            original_attributes,
+            output_payment.clone(),
+            base_rewards_payment.clone(),
+            rewards_receiver,
+            StorageCache::new(self),
+        );
+        self.emit_closed_guild_exit_event(
+            caller,
+            exit_mode,
+            output_payment.clone(),
+            base_rewards_payment.clone(),
+        );
+
+        (output_payment, base_rewards_payment).into()
+    }
+
     fn unstake_farm_common_no_unbond_token_mint(
         &self,
         original_caller: ManagedAddress,
         payment: EsdtTokenPayment,
     ) -> UnstakeCommonNoTokenMintResultType<Self, StakingFarmTokenAttributes<Self::Api>> {
-        let exit_result =
+        let last_migrated_nonce = self.get_last_migrated_farm_token_nonce(&original_caller);
+        self.require_not_migrated_token(payment.token_nonce, last_migrated_nonce);
+
+        let mut exit_result =
             self.exit_farm_base::<FarmStakingWrapper<Self>>(original_caller.clone(), payment);
 
-        let original_attributes = exit_result.original_token_attributes.clone();
-        let base_tokens_removed = original_attributes.get_initial_farming_tokens();
-        self.remove_total_base_staked_tokens(&base_tokens_removed);
-        self.remove_tokens(&original_caller, &original_attributes.get_total_supply());
+        let mut original_attributes = exit_result.original_token_attributes.clone();
+        let slashed_tokens =
+            self.remove_tokens(&original_caller, &original_attributes.get_total_supply());
+        self.remove_compounded_tokens(&original_caller, &original_attributes.compounded_reward);
+
+        // slashed tokens were already removed from the total when slashing
+        let initial_farming_tokens = original_attributes.get_initial_farming_tokens();
+        if initial_farming_tokens > slashed_tokens {
+            self.remove_total_base_staked_tokens(&(initial_farming_tokens - &slashed_tokens));
+        }
+        exit_result.farming_token_payment.amount -= &slashed_tokens;
+
+        // the unbond token may not restore the slashed tokens
+        if slashed_tokens > 0 {
+            original_attributes.current_farm_amount -= &slashed_tokens;
+            if original_attributes.compounded_reward > original_attributes.current_farm_amount {
+                original_attributes.compounded_reward =
+                    original_attributes.current_farm_amount.clone();
+            }
+        }
 
         let reward_token_id = self.reward_token_id().get();
         let base_rewards_payment =
@@ -114,6 +221,7 @@ pub trait UnstakeFarmModule:
     fn multi_unstake(
         &self,
         caller: &ManagedAddress,
+        rewards_receiver: &ManagedAddress,
         payments: &PaymentsVec<Self::Api>,
     ) -> MultiUnstakeResultType<Self::Api> {
         let mut total_rewards = BigUint::zero();
@@ -130,10 +238,15 @@ pub trait UnstakeFarmModule:
                 None => opt_original_attributes = Some(unstake_result.original_attributes),
             }
         }
+        self.remove_officer_below_min_stake(caller);
+
+        total_rewards += self
+            .reward_reserve()
+            .update(|reward_reserve| self.claim_boosted_yields_rewards(caller, reward_reserve));
 
         let reward_token_id = self.reward_token_id().get();
         let reward_payment = EsdtTokenPayment::new(reward_token_id, 0, total_rewards);
-        self.send_payment_non_zero(caller, &reward_payment);
+        self.send_payment_non_zero(rewards_receiver, &reward_payment);
 
         let farming_token_id = self.farming_token_id().get();
         let farming_tokens_payment =
@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           36
+// Endpoints:                           97
 // Async Callback:                       1
-// Total number of exported functions:  39
+// Total number of exported functions: 100
 
 #![no_std]
 
@@ -25,10 +25,17 @@ multiversx_sc_wasm_adapter::endpoints! {
         calculateRewardsForGivenPosition => calculate_rewards_for_given_position
         topUpRewards => top_up_rewards
         startProduceRewards => start_produce_rewards_endpoint
+        stopProduceRewards => stop_produce_rewards_endpoint
+        checkGuildMasterCollateral => check_guild_master_collateral_endpoint
+        withdrawProtocolFees => withdraw_protocol_fees
         getAccumulatedRewards => accumulated_rewards
         getRewardCapacity => reward_capacity
+        getAccruedProtocolFees => accrued_protocol_fees
+        getTotalProtocolFees => total_protocol_fees
         getGuildMasterRewardPerShare => guild_master_rps
+        getOfficerRewardPerShare => officer_rps
         getUserRewardPerShare => user_rps
+        getRpsCheckpoint => rps_checkpoint
         getRewardReserve => reward_reserve
         getFarmingTokenId => farming_token_id
         getRewardTokenId => reward_token_id
@@ -44,18 +51,72 @@ multiversx_sc_wasm_adapter::endpoints! {
         updateOwnerOrAdmin => update_owner_or_admin_endpoint
         getPermissions => permissions
         stakeFarm => stake_farm_endpoint
+        stakeFarmFor => stake_farm_for
+        stakeFarmWithSwap => stake_farm_with_swap
         claimRewards => claim_rewards
+        claimRewardsTo => claim_rewards_to
+        claimRewardsAndSwap => claim_rewards_and_swap
         compoundRewards => compound_rewards
         unstakeFarm => unstake_farm
+        unstakeFarmTo => unstake_farm_to
         unbondFarm => unbond_farm
+        unbondFarmAndSwap => unbond_farm_and_swap
         cancelUnbond => cancel_unbond
         registerUnbondToken => register_unbond_token
         setTransferRoleUnbondToken => set_transfer_role_unbond_token
         getUnbondTokenId => unbond_token
+        isGuildPaused => guild_paused
+        getOfficers => officers
         getUserStakedTokens => get_user_staked_tokens
+        getOfficerTokens => officer_tokens
+        getSlashedGuildMasterTokens => slashed_guild_master_tokens
+        getActiveProposals => active_proposals
+        getGuildMasterCandidateProposals => guild_master_candidate_proposals
         closeGuild => close_guild
+        reopenGuild => reopen_guild
         migrateToOtherGuild => migrate_to_other_guild
+        switchGuild => switch_guild
+        cancelUnbondToGuild => cancel_unbond_to_guild
+        receiveSwitchedPosition => receive_switched_position
+        canReceiveSwitchedPosition => can_receive_switched_position
+        migrateMembers => migrate_members
+        claimMigratedRewards => claim_migrated_rewards
         isGuildClosing => guild_closing
+        getMigratedPositions => migrated_positions
+        setUserUnbondEpochs => set_user_unbond_epochs
+        clearUserUnbondEpochs => clear_user_unbond_epochs
+        getUserUnbondEpochs => get_user_unbond_epochs
+        getOfficerUnbondEpochs => get_officer_unbond_epochs
+        getDistributedRewards => get_distributed_rewards
+        getMembers => members
+        distributeRewards => distribute_rewards
+        syncMemberRewards => sync_member_rewards
+        claimBoostedRewards => claim_boosted_rewards
+        updateEnergyForUser => update_energy_for_user
+        getCurrentWeek => get_current_week
+        getUnclaimedBoostedRewards => get_unclaimed_boosted_rewards
+        getFirstWeekStartEpoch => first_week_start_epoch
+        getAccumulatedRewardsForWeek => accumulated_rewards_for_week
+        getClaimedRewardsForWeek => claimed_rewards_for_week
+        getTotalEnergyForWeek => total_energy_for_week
+        getTotalFarmAmountForWeek => total_farm_amount_for_week
+        getUserBoostedYieldsProgress => user_boosted_yields_progress
+        slashGuildMaster => slash_guild_master
+        createProposal => create_proposal
+        vote => vote
+        executeProposal => execute_proposal
+        cancelProposal => cancel_proposal
+        getVotingPower => get_voting_power
+        getLastProposalId => last_proposal_id
+        getProposal => proposal
+        hasVoted => has_voted
+        addOfficer => add_officer
+        removeOfficer => remove_officer
+        startSponsorCampaign => start_sponsor_campaign
+        endSponsorCampaign => end_sponsor_campaign
+        getSponsorCampaignRemainingRewards => get_sponsor_campaign_remaining_rewards
+        getSponsorCampaign => sponsor_campaign
+        getUndercollateralizedDeadlineEpoch => undercollateralized_deadline_epoch
     )
 }
 